@@ -0,0 +1,211 @@
+#ifndef PI_COLLISION_H
+#define PI_COLLISION_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// Define PI_COLLISION_F64 when the library is built with the `f64` feature.
+#if !defined(PI_COLLISION_F32) && !defined(PI_COLLISION_F64)
+#define PI_COLLISION_F32
+#endif
+
+typedef enum PiCollisionError {
+  PI_COLLISION_ERROR_OK = 0,
+  PI_COLLISION_ERROR_NULL_POINTER = 1,
+  PI_COLLISION_ERROR_INVALID_ARGUMENT = 2,
+  PI_COLLISION_ERROR_UNSUPPORTED = 3,
+  PI_COLLISION_ERROR_NO_HIT = 4,
+  PI_COLLISION_ERROR_PANIC = 5,
+} PiCollisionError;
+
+typedef struct PiCollisionShape PiCollisionShape;
+
+#if !defined(PI_COLLISION_F64)
+typedef float PiCollisionReal;
+#endif
+
+#if defined(PI_COLLISION_F64)
+typedef double PiCollisionReal;
+#endif
+
+typedef struct PiCollisionVec3 {
+  PiCollisionReal x;
+  PiCollisionReal y;
+  PiCollisionReal z;
+} PiCollisionVec3;
+
+typedef struct PiCollisionContact {
+  struct PiCollisionVec3 point1;
+  struct PiCollisionVec3 point2;
+  struct PiCollisionVec3 normal1;
+  struct PiCollisionVec3 normal2;
+  PiCollisionReal dist;
+} PiCollisionContact;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_point(struct PiCollisionVec3 point,
+                                                   struct PiCollisionShape **out);
+
+/**
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_ray(struct PiCollisionVec3 origin,
+                                                 struct PiCollisionVec3 dir,
+                                                 struct PiCollisionShape **out);
+
+/**
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_plane(struct PiCollisionVec3 normal,
+                                                   struct PiCollisionVec3 point,
+                                                   struct PiCollisionShape **out);
+
+/**
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_sphere(struct PiCollisionVec3 center,
+                                                    PiCollisionReal radius,
+                                                    struct PiCollisionShape **out);
+
+/**
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_ball(PiCollisionReal radius,
+                                                  struct PiCollisionShape **out);
+
+/**
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_aabb(struct PiCollisionVec3 mins,
+                                                  struct PiCollisionVec3 maxs,
+                                                  struct PiCollisionShape **out);
+
+/**
+ * `axis_angle` is the rotation as in `Obb::new`.
+ *
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_obb(struct PiCollisionVec3 center,
+                                                 struct PiCollisionVec3 axis_angle,
+                                                 struct PiCollisionVec3 half_extents,
+                                                 struct PiCollisionShape **out);
+
+/**
+ * # Safety
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_triangle(struct PiCollisionVec3 a,
+                                                      struct PiCollisionVec3 b,
+                                                      struct PiCollisionVec3 c,
+                                                      struct PiCollisionShape **out);
+
+/**
+ * The frustum is the convex hull of `count` points, `InvalidArgument` when they are
+ * fewer than 4, repeated or flat.
+ *
+ * # Safety
+ * `points` must point to `count` values and `out` must be a valid pointer,
+ * the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_frustum(const struct PiCollisionVec3 *points,
+                                                     size_t count,
+                                                     struct PiCollisionShape **out);
+
+/**
+ * `indices` holds `index_count / 3` triangles referencing the `count` vertices.
+ *
+ * # Safety
+ * `vertices` must point to `count` values, `indices` to `index_count` values and
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_trimesh(const struct PiCollisionVec3 *vertices,
+                                                     size_t count,
+                                                     const uint32_t *indices,
+                                                     size_t index_count,
+                                                     struct PiCollisionShape **out);
+
+/**
+ * The shape is the convex hull of `count` points.
+ *
+ * # Safety
+ * `points` must point to `count` values and `out` must be a valid pointer,
+ * the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_convex_hull(const struct PiCollisionVec3 *points,
+                                                         size_t count,
+                                                         struct PiCollisionShape **out);
+
+/**
+ * The parts are copied, `shapes` still has to be released by the caller.
+ *
+ * # Safety
+ * `shapes` must point to `count` shapes from `pi_collision_shape_new_*` functions and
+ * `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+ */
+enum PiCollisionError pi_collision_shape_new_compound(const struct PiCollisionShape *const *shapes,
+                                                      size_t count,
+                                                      struct PiCollisionShape **out);
+
+/**
+ * # Safety
+ * `shape` must come from a `pi_collision_shape_new_*` function and is not used afterwards.
+ */
+void pi_collision_shape_free(struct PiCollisionShape *shape);
+
+/**
+ * # Safety
+ * All pointers must be valid.
+ */
+enum PiCollisionError pi_collision_test(const struct PiCollisionShape *shape1,
+                                        const struct PiCollisionShape *shape2,
+                                        bool *out_hit);
+
+/**
+ * Returns `NoHit` when the ray misses within `max_toi`.
+ *
+ * # Safety
+ * All pointers must be valid.
+ */
+enum PiCollisionError pi_collision_raycast(struct PiCollisionVec3 origin,
+                                           struct PiCollisionVec3 dir,
+                                           const struct PiCollisionShape *shape,
+                                           PiCollisionReal max_toi,
+                                           PiCollisionReal *out_toi);
+
+/**
+ * # Safety
+ * All pointers must be valid.
+ */
+enum PiCollisionError pi_collision_distance(const struct PiCollisionShape *shape1,
+                                            const struct PiCollisionShape *shape2,
+                                            PiCollisionReal *out_distance);
+
+/**
+ * Returns `NoHit` when the shapes are further apart than `prediction`.
+ *
+ * # Safety
+ * All pointers must be valid.
+ */
+enum PiCollisionError pi_collision_contact(const struct PiCollisionShape *shape1,
+                                           const struct PiCollisionShape *shape2,
+                                           PiCollisionReal prediction,
+                                           struct PiCollisionContact *out_contact);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* PI_COLLISION_H */
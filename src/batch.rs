@@ -0,0 +1,179 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::shape::{Frustum, Ray, Real};
+use crate::{
+    compute_aabb_and_frustum, compute_frustum_and_frustum, compute_obb_and_frustum,
+    compute_plane_and_frustum, compute_sphere_and_frustum, compute_triangle_and_frustum, raycast,
+    test_collision, try_test_collision, Geomery,
+};
+use parry3d::query::PointQuery;
+
+#[allow(unused_imports)]
+use crate::shape::{
+    Aabb, Ball, ConvexHull, ConvexPolyhedron, Isometry, MTriangle, Obb, Plane, Point3, Sphere,
+    Triangle, Vector3,
+};
+
+fn for_each_into<T, R, F>(input: &[T], out: &mut [R], f: F)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    assert_eq!(
+        input.len(),
+        out.len(),
+        "batch output buffer length mismatch!!! input: {}, output: {}",
+        input.len(),
+        out.len()
+    );
+
+    #[cfg(feature = "rayon")]
+    input
+        .par_iter()
+        .zip(out.par_iter_mut())
+        .for_each(|(i, o)| *o = f(i));
+
+    #[cfg(not(feature = "rayon"))]
+    input
+        .iter()
+        .zip(out.iter_mut())
+        .for_each(|(i, o)| *o = f(i));
+}
+
+pub fn test_collision_many(geomery: &Geomery, others: &[Geomery], out: &mut [bool]) {
+    for_each_into(others, out, |other| test_collision(geomery, other));
+}
+
+pub fn raycast_many(rays: &[Ray], geomery: &Geomery, max_toi: Real, out: &mut [Option<Real>]) {
+    for_each_into(rays, out, |ray| raycast(ray, geomery, max_toi));
+}
+
+pub fn cull(frustum: &Frustum, geomeries: &[Geomery], out: &mut [bool]) {
+    let target = Geomery::Frustum(frustum.clone());
+    for_each_into(geomeries, out, |geomery| match geomery {
+        Geomery::Point(p) => frustum.0.contains_point(&frustum.1, p),
+        Geomery::Sphere(s) => compute_sphere_and_frustum(s, frustum),
+        Geomery::Aabb(ab) => compute_aabb_and_frustum(ab, frustum),
+        Geomery::Obb(obb) => compute_obb_and_frustum(obb, frustum),
+        Geomery::Plane(p) => compute_plane_and_frustum(p, frustum),
+        Geomery::Triangle(t) => compute_triangle_and_frustum(t, frustum),
+        Geomery::Frustum(f) => compute_frustum_and_frustum(f, frustum),
+        // Pairs without a collision test are kept, culling must not hide visible geomery.
+        g => try_test_collision(g, &target).unwrap_or(true),
+    });
+}
+
+#[cfg(test)]
+use crate::shape::test_frustum;
+
+#[test]
+fn test_test_collision_many() {
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::identity()));
+
+    let others = vec![
+        Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(1.0, 0.0, 0.0))),
+        Geomery::Aabb(Aabb::new(
+            Point3::new(3.0, 3.0, 3.0),
+            Point3::new(4.0, 4.0, 4.0),
+        )),
+        Geomery::Aabb(Aabb::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 1.0),
+        )),
+    ];
+    let mut out = vec![false; others.len()];
+    test_collision_many(&sphere, &others, &mut out);
+    assert_eq!(out, vec![true, false, true]);
+}
+
+#[test]
+fn test_raycast_many() {
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(3.0, 0.0, 0.0)));
+
+    let rays = vec![
+        Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0)),
+        Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0)),
+        Ray::new(Point3::new(3.0, 5.0, 0.0), Vector3::new(0.0, -1.0, 0.0)),
+    ];
+    let mut out = vec![None; rays.len()];
+    raycast_many(&rays, &sphere, Real::MAX, &mut out);
+    assert_eq!(out, vec![Some(2.0), None, Some(4.0)]);
+}
+
+#[test]
+fn test_cull() {
+    let frustum = test_frustum();
+
+    let geomeries = vec![
+        Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::identity())),
+        Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(0.0, 0.0, 2.0))),
+        Geomery::Aabb(Aabb::new(
+            Point3::new(0.0, 0.0, -3.0),
+            Point3::new(1.0, 1.0, -2.0),
+        )),
+        Geomery::Aabb(Aabb::new(
+            Point3::new(10.0, 0.0, -3.0),
+            Point3::new(11.0, 1.0, -2.0),
+        )),
+        Geomery::Point(Point3::new(0.0, 0.0, -1.0)),
+        Geomery::Obb(Obb::new(
+            Point3::new(0.0, 0.0, 1.3),
+            Vector3::new(0.0, std::f64::consts::FRAC_PI_4 as Real, 0.0),
+            Vector3::new(1.0, 1.0, 1.0),
+        )),
+        Geomery::Plane(Plane::new(Point3::new(0.0, 0.0, 1.0), Vector3::z()).unwrap()),
+        Geomery::Triangle(Triangle(
+            MTriangle::new(
+                Point3::new(-10.0, 0.0, -2.0),
+                Point3::new(10.0, 0.0, -2.0),
+                Point3::new(0.0, 0.0, 10.0),
+            ),
+            Isometry::identity(),
+        )),
+        Geomery::Frustum(Frustum(
+            test_frustum().0,
+            Isometry::translation(0.0, 0.0, -5.5),
+        )),
+    ];
+    let mut out = vec![false; geomeries.len()];
+    cull(&frustum, &geomeries, &mut out);
+    assert_eq!(
+        out,
+        vec![true, false, true, false, true, true, false, true, false]
+    );
+}
+
+#[test]
+fn test_cull_other_kinds() {
+    let frustum = test_frustum();
+    let cube = |min: Point3| {
+        let max = min + Vector3::new(1.0, 1.0, 1.0);
+        Geomery::ConvexHull(ConvexHull::new(&Aabb::new(min, max).corners()).unwrap())
+    };
+    let sphere =
+        |z: Real| Geomery::Sphere(Sphere(Ball::new(0.5), Isometry::translation(0.0, 0.0, z)));
+
+    let geomeries = vec![
+        cube(Point3::new(0.0, 0.0, -3.0)),
+        cube(Point3::new(20.0, 0.0, -3.0)),
+        Geomery::Compound(vec![sphere(5.0), sphere(-3.0)]),
+        Geomery::Compound(vec![sphere(5.0), cube(Point3::new(20.0, 0.0, -3.0))]),
+        // Ball and frustum have no test, the ball is kept.
+        Geomery::Ball(Ball::new(1.0)),
+    ];
+    let mut out = vec![false; geomeries.len()];
+    cull(&frustum, &geomeries, &mut out);
+    assert_eq!(out, vec![true, false, true, false, true]);
+}
+
+#[test]
+#[should_panic]
+fn test_cull_output_length_mismatch() {
+    let frustum = test_frustum();
+
+    let geomeries = vec![Geomery::Point(Point3::new(0.0, 0.0, -1.0))];
+    let mut out = vec![];
+    cull(&frustum, &geomeries, &mut out);
+}
@@ -0,0 +1,732 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::shape::{
+    Aabb, Ball, ConvexHull, Frustum, Isometry, MTriangle, Obb, Plane, Point3, Ray, Sphere, TriMesh,
+    Triangle, Vector3,
+};
+use crate::Geomery;
+
+#[cfg(not(feature = "f64"))]
+pub type PiCollisionReal = f32;
+#[cfg(feature = "f64")]
+pub type PiCollisionReal = f64;
+
+pub struct PiCollisionShape(Geomery);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiCollisionError {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidArgument = 2,
+    Unsupported = 3,
+    NoHit = 4,
+    Panic = 5,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PiCollisionVec3 {
+    pub x: PiCollisionReal,
+    pub y: PiCollisionReal,
+    pub z: PiCollisionReal,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PiCollisionContact {
+    pub point1: PiCollisionVec3,
+    pub point2: PiCollisionVec3,
+    pub normal1: PiCollisionVec3,
+    pub normal2: PiCollisionVec3,
+    pub dist: PiCollisionReal,
+}
+
+impl PiCollisionVec3 {
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    fn point(&self) -> Point3 {
+        Point3::new(self.x, self.y, self.z)
+    }
+
+    fn vector(&self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+}
+
+impl From<Vector3> for PiCollisionVec3 {
+    fn from(v: Vector3) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+// Shapes are only read by queries, a panic can not leave them half updated.
+fn guard<F: FnOnce() -> PiCollisionError>(f: F) -> PiCollisionError {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(PiCollisionError::Panic)
+}
+
+unsafe fn new_shape(
+    out: *mut *mut PiCollisionShape,
+    f: impl FnOnce() -> Result<Geomery, PiCollisionError>,
+) -> PiCollisionError {
+    if out.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    *out = ptr::null_mut();
+
+    guard(|| match f() {
+        Ok(geomery) => {
+            *out = Box::into_raw(Box::new(PiCollisionShape(geomery)));
+            PiCollisionError::Ok
+        }
+        Err(e) => e,
+    })
+}
+
+fn check(valid: bool) -> Result<(), PiCollisionError> {
+    if valid {
+        Ok(())
+    } else {
+        Err(PiCollisionError::InvalidArgument)
+    }
+}
+
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_point(
+    point: PiCollisionVec3,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(point.is_finite())?;
+        Ok(Geomery::Point(point.point()))
+    })
+}
+
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_ray(
+    origin: PiCollisionVec3,
+    dir: PiCollisionVec3,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(origin.is_finite() && dir.is_finite())?;
+        Ok(Geomery::Ray(Ray::new(origin.point(), dir.vector())))
+    })
+}
+
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_plane(
+    normal: PiCollisionVec3,
+    point: PiCollisionVec3,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(normal.is_finite() && point.is_finite())?;
+        let plane =
+            Plane::new(point.point(), normal.vector()).ok_or(PiCollisionError::InvalidArgument)?;
+        Ok(Geomery::Plane(plane))
+    })
+}
+
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_sphere(
+    center: PiCollisionVec3,
+    radius: PiCollisionReal,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(center.is_finite() && radius.is_finite() && radius >= 0.0)?;
+        Ok(Geomery::Sphere(Sphere(
+            Ball::new(radius),
+            Isometry::translation(center.x, center.y, center.z),
+        )))
+    })
+}
+
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_ball(
+    radius: PiCollisionReal,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(radius.is_finite() && radius >= 0.0)?;
+        Ok(Geomery::Ball(Ball::new(radius)))
+    })
+}
+
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_aabb(
+    mins: PiCollisionVec3,
+    maxs: PiCollisionVec3,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(mins.is_finite() && maxs.is_finite())?;
+        Ok(Geomery::Aabb(Aabb::new(mins.point(), maxs.point())))
+    })
+}
+
+/// `axis_angle` is the rotation as in `Obb::new`.
+///
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_obb(
+    center: PiCollisionVec3,
+    axis_angle: PiCollisionVec3,
+    half_extents: PiCollisionVec3,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(center.is_finite() && axis_angle.is_finite() && half_extents.is_finite())?;
+        Ok(Geomery::Obb(Obb::new(
+            center.point(),
+            axis_angle.vector(),
+            half_extents.vector(),
+        )))
+    })
+}
+
+/// # Safety
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_triangle(
+    a: PiCollisionVec3,
+    b: PiCollisionVec3,
+    c: PiCollisionVec3,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    new_shape(out, || {
+        check(a.is_finite() && b.is_finite() && c.is_finite())?;
+        Ok(Geomery::Triangle(Triangle(
+            MTriangle::new(a.point(), b.point(), c.point()),
+            Isometry::identity(),
+        )))
+    })
+}
+
+/// The frustum is the convex hull of `count` points, `InvalidArgument` when they are
+/// fewer than 4, repeated or flat.
+///
+/// # Safety
+/// `points` must point to `count` values and `out` must be a valid pointer,
+/// the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_frustum(
+    points: *const PiCollisionVec3,
+    count: usize,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    if points.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    let points = std::slice::from_raw_parts(points, count);
+
+    new_shape(out, || {
+        check(points.iter().all(|p| p.is_finite()))?;
+        let points: Vec<Point3> = points.iter().map(|p| p.point()).collect();
+        let frustum = Frustum::from_points(&points).ok_or(PiCollisionError::InvalidArgument)?;
+        Ok(Geomery::Frustum(frustum))
+    })
+}
+
+/// `indices` holds `index_count / 3` triangles referencing the `count` vertices.
+///
+/// # Safety
+/// `vertices` must point to `count` values, `indices` to `index_count` values and
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_trimesh(
+    vertices: *const PiCollisionVec3,
+    count: usize,
+    indices: *const u32,
+    index_count: usize,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    if vertices.is_null() || indices.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    let vertices = std::slice::from_raw_parts(vertices, count);
+    let indices = std::slice::from_raw_parts(indices, index_count);
+
+    new_shape(out, || {
+        check(index_count.is_multiple_of(3) && vertices.iter().all(|p| p.is_finite()))?;
+        let mesh = TriMesh::new(
+            vertices.iter().map(|p| p.point()).collect(),
+            indices
+                .chunks_exact(3)
+                .map(|t| [t[0], t[1], t[2]])
+                .collect(),
+        )
+        .ok_or(PiCollisionError::InvalidArgument)?;
+        Ok(Geomery::TriMesh(mesh))
+    })
+}
+
+/// The shape is the convex hull of `count` points.
+///
+/// # Safety
+/// `points` must point to `count` values and `out` must be a valid pointer,
+/// the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_convex_hull(
+    points: *const PiCollisionVec3,
+    count: usize,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    if points.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    let points = std::slice::from_raw_parts(points, count);
+
+    new_shape(out, || {
+        check(points.iter().all(|p| p.is_finite()))?;
+        let points: Vec<Point3> = points.iter().map(|p| p.point()).collect();
+        let hull = ConvexHull::new(&points).ok_or(PiCollisionError::InvalidArgument)?;
+        Ok(Geomery::ConvexHull(hull))
+    })
+}
+
+/// The parts are copied, `shapes` still has to be released by the caller.
+///
+/// # Safety
+/// `shapes` must point to `count` shapes from `pi_collision_shape_new_*` functions and
+/// `out` must be a valid pointer, the returned shape is released with `pi_collision_shape_free`.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_new_compound(
+    shapes: *const *const PiCollisionShape,
+    count: usize,
+    out: *mut *mut PiCollisionShape,
+) -> PiCollisionError {
+    if shapes.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    let shapes = std::slice::from_raw_parts(shapes, count);
+    if shapes.iter().any(|s| s.is_null()) {
+        return PiCollisionError::NullPointer;
+    }
+
+    new_shape(out, || {
+        let parts = shapes.iter().map(|s| (**s).0.clone()).collect();
+        Ok(Geomery::Compound(parts))
+    })
+}
+
+/// # Safety
+/// `shape` must come from a `pi_collision_shape_new_*` function and is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_shape_free(shape: *mut PiCollisionShape) {
+    if !shape.is_null() {
+        drop(Box::from_raw(shape));
+    }
+}
+
+/// # Safety
+/// All pointers must be valid.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_test(
+    shape1: *const PiCollisionShape,
+    shape2: *const PiCollisionShape,
+    out_hit: *mut bool,
+) -> PiCollisionError {
+    if shape1.is_null() || shape2.is_null() || out_hit.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    let (g1, g2) = (&(*shape1).0, &(*shape2).0);
+
+    guard(|| match crate::try_test_collision(g1, g2) {
+        Some(hit) => {
+            *out_hit = hit;
+            PiCollisionError::Ok
+        }
+        None => PiCollisionError::Unsupported,
+    })
+}
+
+/// Returns `NoHit` when the ray misses within `max_toi`.
+///
+/// # Safety
+/// All pointers must be valid.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_raycast(
+    origin: PiCollisionVec3,
+    dir: PiCollisionVec3,
+    shape: *const PiCollisionShape,
+    max_toi: PiCollisionReal,
+    out_toi: *mut PiCollisionReal,
+) -> PiCollisionError {
+    if shape.is_null() || out_toi.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    if !origin.is_finite() || !dir.is_finite() || max_toi.is_nan() {
+        return PiCollisionError::InvalidArgument;
+    }
+    let geomery = &(*shape).0;
+
+    guard(|| {
+        let ray = Ray::new(origin.point(), dir.vector());
+        match crate::raycast(&ray, geomery, max_toi) {
+            Some(toi) => {
+                *out_toi = toi;
+                PiCollisionError::Ok
+            }
+            None => PiCollisionError::NoHit,
+        }
+    })
+}
+
+/// # Safety
+/// All pointers must be valid.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_distance(
+    shape1: *const PiCollisionShape,
+    shape2: *const PiCollisionShape,
+    out_distance: *mut PiCollisionReal,
+) -> PiCollisionError {
+    if shape1.is_null() || shape2.is_null() || out_distance.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    let (g1, g2) = (&(*shape1).0, &(*shape2).0);
+
+    guard(|| match crate::distance(g1, g2) {
+        Ok(d) => {
+            *out_distance = d;
+            PiCollisionError::Ok
+        }
+        Err(_) => PiCollisionError::Unsupported,
+    })
+}
+
+/// Returns `NoHit` when the shapes are further apart than `prediction`.
+///
+/// # Safety
+/// All pointers must be valid.
+#[no_mangle]
+pub unsafe extern "C" fn pi_collision_contact(
+    shape1: *const PiCollisionShape,
+    shape2: *const PiCollisionShape,
+    prediction: PiCollisionReal,
+    out_contact: *mut PiCollisionContact,
+) -> PiCollisionError {
+    if shape1.is_null() || shape2.is_null() || out_contact.is_null() {
+        return PiCollisionError::NullPointer;
+    }
+    let (g1, g2) = (&(*shape1).0, &(*shape2).0);
+
+    guard(|| match crate::contact(g1, g2, prediction) {
+        Ok(Some(c)) => {
+            *out_contact = PiCollisionContact {
+                point1: c.point1.coords.into(),
+                point2: c.point2.coords.into(),
+                normal1: c.normal1.into_inner().into(),
+                normal2: c.normal2.into_inner().into(),
+                dist: c.dist,
+            };
+            PiCollisionError::Ok
+        }
+        Ok(None) => PiCollisionError::NoHit,
+        Err(_) => PiCollisionError::Unsupported,
+    })
+}
+
+#[cfg(test)]
+fn vec3(x: PiCollisionReal, y: PiCollisionReal, z: PiCollisionReal) -> PiCollisionVec3 {
+    PiCollisionVec3 { x, y, z }
+}
+
+#[test]
+fn test_capi_test() {
+    unsafe {
+        let mut sphere = ptr::null_mut();
+        let mut point = ptr::null_mut();
+        let mut aabb = ptr::null_mut();
+        assert_eq!(
+            pi_collision_shape_new_sphere(vec3(0.0, 0.0, 0.0), 1.0, &mut sphere),
+            PiCollisionError::Ok
+        );
+        assert_eq!(
+            pi_collision_shape_new_point(vec3(0.5, 0.0, 0.0), &mut point),
+            PiCollisionError::Ok
+        );
+        assert_eq!(
+            pi_collision_shape_new_aabb(vec3(2.0, 2.0, 2.0), vec3(3.0, 3.0, 3.0), &mut aabb),
+            PiCollisionError::Ok
+        );
+
+        let mut hit = false;
+        assert_eq!(
+            pi_collision_test(point, sphere, &mut hit),
+            PiCollisionError::Ok
+        );
+        assert_eq!(hit, true);
+        assert_eq!(
+            pi_collision_test(sphere, aabb, &mut hit),
+            PiCollisionError::Ok
+        );
+        assert_eq!(hit, false);
+        assert_eq!(
+            pi_collision_test(sphere, point, &mut hit),
+            PiCollisionError::Unsupported
+        );
+        assert_eq!(
+            pi_collision_test(sphere, ptr::null(), &mut hit),
+            PiCollisionError::NullPointer
+        );
+
+        pi_collision_shape_free(sphere);
+        pi_collision_shape_free(point);
+        pi_collision_shape_free(aabb);
+    }
+}
+
+#[test]
+fn test_capi_queries() {
+    unsafe {
+        let mut sphere = ptr::null_mut();
+        let mut other = ptr::null_mut();
+        pi_collision_shape_new_sphere(vec3(3.0, 0.0, 0.0), 1.0, &mut sphere);
+        pi_collision_shape_new_sphere(vec3(0.0, 0.0, 0.0), 1.0, &mut other);
+
+        let mut toi = 0.0;
+        assert_eq!(
+            pi_collision_raycast(
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                sphere,
+                PiCollisionReal::MAX,
+                &mut toi
+            ),
+            PiCollisionError::Ok
+        );
+        assert_eq!(toi, 2.0);
+        assert_eq!(
+            pi_collision_raycast(
+                vec3(0.0, 0.0, 0.0),
+                vec3(-1.0, 0.0, 0.0),
+                sphere,
+                10.0,
+                &mut toi
+            ),
+            PiCollisionError::NoHit
+        );
+
+        let mut d = 0.0;
+        assert_eq!(
+            pi_collision_distance(sphere, other, &mut d),
+            PiCollisionError::Ok
+        );
+        assert_eq!(d, 1.0);
+
+        let mut c = PiCollisionContact {
+            point1: vec3(0.0, 0.0, 0.0),
+            point2: vec3(0.0, 0.0, 0.0),
+            normal1: vec3(0.0, 0.0, 0.0),
+            normal2: vec3(0.0, 0.0, 0.0),
+            dist: 0.0,
+        };
+        assert_eq!(
+            pi_collision_contact(other, sphere, 0.0, &mut c),
+            PiCollisionError::NoHit
+        );
+        assert_eq!(
+            pi_collision_contact(other, sphere, 2.0, &mut c),
+            PiCollisionError::Ok
+        );
+        assert_eq!(c.dist, 1.0);
+        assert_eq!(c.normal1, vec3(1.0, 0.0, 0.0));
+
+        pi_collision_shape_free(sphere);
+        pi_collision_shape_free(other);
+    }
+}
+
+#[test]
+fn test_capi_invalid_arguments() {
+    unsafe {
+        let mut shape = ptr::null_mut();
+        assert_eq!(
+            pi_collision_shape_new_sphere(vec3(0.0, 0.0, 0.0), -1.0, &mut shape),
+            PiCollisionError::InvalidArgument
+        );
+        assert!(shape.is_null());
+        assert_eq!(
+            pi_collision_shape_new_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 0.0), &mut shape),
+            PiCollisionError::InvalidArgument
+        );
+        assert_eq!(
+            pi_collision_shape_new_point(vec3(PiCollisionReal::NAN, 0.0, 0.0), &mut shape),
+            PiCollisionError::InvalidArgument
+        );
+        assert_eq!(
+            pi_collision_shape_new_ball(1.0, ptr::null_mut()),
+            PiCollisionError::NullPointer
+        );
+
+        let flat = [
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(1.0, 1.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        ];
+        for points in [
+            &[vec3(0.0, 0.0, 0.0); 3][..],
+            &[vec3(1.0, 2.0, 3.0); 8],
+            &flat,
+        ] {
+            assert_eq!(
+                pi_collision_shape_new_frustum(points.as_ptr(), points.len(), &mut shape),
+                PiCollisionError::InvalidArgument
+            );
+        }
+
+        pi_collision_shape_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn test_capi_every_shape() {
+    unsafe {
+        let mut shapes = vec![ptr::null_mut(); 9];
+        pi_collision_shape_new_point(vec3(0.0, 0.0, -1.0), &mut shapes[0]);
+        pi_collision_shape_new_ray(vec3(0.0, 0.0, 0.0), vec3(1.0, 1.0, 1.0), &mut shapes[1]);
+        pi_collision_shape_new_plane(vec3(0.0, 1.0, 0.0), vec3(0.0, -1.0, 0.0), &mut shapes[2]);
+        pi_collision_shape_new_sphere(vec3(0.0, 0.0, 0.0), 1.0, &mut shapes[3]);
+        pi_collision_shape_new_aabb(vec3(0.0, 0.0, 0.0), vec3(1.0, 1.0, 1.0), &mut shapes[4]);
+        pi_collision_shape_new_ball(1.0, &mut shapes[5]);
+        pi_collision_shape_new_triangle(
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            &mut shapes[6],
+        );
+        let points = [
+            vec3(2.0, 1.0, 0.0),
+            vec3(-2.0, 1.0, 0.0),
+            vec3(-2.0, -1.0, 0.0),
+            vec3(2.0, -1.0, 0.0),
+            vec3(3.0, 2.0, -5.0),
+            vec3(-3.0, 2.0, -5.0),
+            vec3(-3.0, -2.0, -5.0),
+            vec3(3.0, -2.0, -5.0),
+        ];
+        assert_eq!(
+            pi_collision_shape_new_frustum(points.as_ptr(), points.len(), &mut shapes[7]),
+            PiCollisionError::Ok
+        );
+        pi_collision_shape_new_obb(
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 1.0, 1.0),
+            vec3(1.0, 1.0, 1.0),
+            &mut shapes[8],
+        );
+        assert!(shapes.iter().all(|s| !s.is_null()));
+
+        let mut hit = false;
+        assert_eq!(
+            pi_collision_test(shapes[3], shapes[7], &mut hit),
+            PiCollisionError::Ok
+        );
+        assert_eq!(hit, true);
+        assert_eq!(
+            pi_collision_test(shapes[1], shapes[6], &mut hit),
+            PiCollisionError::Ok
+        );
+        assert_eq!(hit, true);
+        assert_eq!(
+            pi_collision_test(shapes[8], shapes[2], &mut hit),
+            PiCollisionError::Ok
+        );
+        assert_eq!(hit, true);
+
+        for shape in shapes {
+            pi_collision_shape_free(shape);
+        }
+    }
+}
+
+#[test]
+fn test_capi_mesh_shapes() {
+    unsafe {
+        let points = [
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            vec3(0.0, 0.0, 1.0),
+        ];
+        let indices = [0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3];
+
+        let mut shapes = vec![ptr::null_mut(); 3];
+        assert_eq!(
+            pi_collision_shape_new_trimesh(
+                points.as_ptr(),
+                points.len(),
+                indices.as_ptr(),
+                indices.len(),
+                &mut shapes[0]
+            ),
+            PiCollisionError::Ok
+        );
+        assert_eq!(
+            pi_collision_shape_new_convex_hull(points.as_ptr(), points.len(), &mut shapes[1]),
+            PiCollisionError::Ok
+        );
+        let parts = [shapes[0] as *const _, shapes[1] as *const _];
+        assert_eq!(
+            pi_collision_shape_new_compound(parts.as_ptr(), parts.len(), &mut shapes[2]),
+            PiCollisionError::Ok
+        );
+
+        let mut sphere = ptr::null_mut();
+        pi_collision_shape_new_sphere(vec3(0.0, 0.0, 2.0), 1.5, &mut sphere);
+        let mut hit = false;
+        for shape in &shapes {
+            assert_eq!(
+                pi_collision_test(*shape, sphere, &mut hit),
+                PiCollisionError::Ok
+            );
+            assert_eq!(hit, true);
+        }
+
+        let mut shape = ptr::null_mut();
+        let bad = [0, 1, 4];
+        assert_eq!(
+            pi_collision_shape_new_trimesh(
+                points.as_ptr(),
+                points.len(),
+                bad.as_ptr(),
+                bad.len(),
+                &mut shape
+            ),
+            PiCollisionError::InvalidArgument
+        );
+        let parts = [shapes[0] as *const _, ptr::null()];
+        assert_eq!(
+            pi_collision_shape_new_compound(parts.as_ptr(), parts.len(), &mut shape),
+            PiCollisionError::NullPointer
+        );
+
+        pi_collision_shape_free(sphere);
+        for shape in shapes {
+            pi_collision_shape_free(shape);
+        }
+    }
+}
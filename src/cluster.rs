@@ -0,0 +1,298 @@
+use crate::compute_sphere_and_aabb;
+use crate::shadow::{split_depths, SplitScheme};
+use crate::shape::{Aabb, Ball, Isometry, Point3, Real, Sphere, Vector3};
+
+#[allow(unused_imports)]
+use crate::shape::ApproxEq;
+
+// Cone of a spot light, `angle` is the half angle in radians, below 90 degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpotLight {
+    pub position: Point3,
+    pub direction: Vector3,
+    pub range: Real,
+    pub angle: Real,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LightVolume {
+    Point(Sphere),
+    Spot(SpotLight),
+}
+
+// Perspective camera cut in `dims` froxels, x and y tiles are uniform on screen
+// and depth slices are logarithmic. Froxels are stored as view space boxes.
+#[derive(Debug, Clone)]
+pub struct FroxelGrid {
+    pub dims: [usize; 3],
+    // World to view space, the camera looks down -z.
+    pub view: Isometry,
+    pub near: Real,
+    pub far: Real,
+    tan_x: Real,
+    tan_y: Real,
+    froxels: Vec<Aabb>,
+}
+
+impl FroxelGrid {
+    // `fov_y` is the full vertical field of view in radians.
+    pub fn new(
+        view: Isometry,
+        fov_y: Real,
+        aspect: Real,
+        near: Real,
+        far: Real,
+        dims: [usize; 3],
+    ) -> Option<Self> {
+        if dims.contains(&0) || fov_y <= 0.0 || aspect <= 0.0 {
+            return None;
+        }
+        let depths = split_depths(near, far, dims[2], SplitScheme::Logarithmic)?;
+        let tan_y = (fov_y * 0.5).tan();
+        let tan_x = tan_y * aspect;
+
+        let mut froxels = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+        for z in depths.windows(2) {
+            for y in 0..dims[1] {
+                for x in 0..dims[0] {
+                    let (x0, x1) = tile(x, dims[0]);
+                    let (y0, y1) = tile(y, dims[1]);
+                    let points = [z[0], z[1]].map(|d| {
+                        [(x0, y0), (x1, y0), (x1, y1), (x0, y1)]
+                            .map(|(x, y)| Point3::new(x * d * tan_x, y * d * tan_y, -d))
+                    });
+                    froxels.push(Aabb::from_points(points.as_flattened()).unwrap());
+                }
+            }
+        }
+
+        Some(Self {
+            dims,
+            view,
+            near,
+            far,
+            tan_x,
+            tan_y,
+            froxels,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.froxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.froxels.is_empty()
+    }
+
+    // x varies fastest, then y, then depth.
+    pub fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        (z * self.dims[1] + y) * self.dims[0] + x
+    }
+
+    // View space box of the froxel.
+    pub fn froxel(&self, index: usize) -> &Aabb {
+        &self.froxels[index]
+    }
+
+    // Froxel holding a world space point, None outside the camera frustum.
+    pub fn froxel_at(&self, point: &Point3) -> Option<usize> {
+        let p = self.view * point;
+        let d = -p.z;
+        if d < self.near || d > self.far {
+            return None;
+        }
+
+        // `t == 1` on the right or top edge still belongs to the last tile.
+        let cell = |t: Real, n: usize| {
+            (-1.0..=1.0)
+                .contains(&t)
+                .then(|| (((t + 1.0) * 0.5 * n as Real) as usize).min(n - 1))
+        };
+        let x = cell(p.x / (d * self.tan_x), self.dims[0])?;
+        let y = cell(p.y / (d * self.tan_y), self.dims[1])?;
+        let z =
+            ((d / self.near).ln() / (self.far / self.near).ln() * self.dims[2] as Real) as usize;
+        Some(self.index(x, y, z.min(self.dims[2] - 1)))
+    }
+
+    // Indices of the lights overlapping each froxel.
+    pub fn assign(&self, lights: &[LightVolume]) -> ClusterLights {
+        let lights: Vec<LightVolume> = lights.iter().map(|l| to_view(l, &self.view)).collect();
+
+        let mut offsets = Vec::with_capacity(self.froxels.len() + 1);
+        let mut indices = Vec::new();
+        offsets.push(0);
+        for froxel in &self.froxels {
+            for (i, light) in lights.iter().enumerate() {
+                if light_and_froxel(light, froxel) {
+                    indices.push(i as u32);
+                }
+            }
+            offsets.push(indices.len() as u32);
+        }
+
+        ClusterLights { offsets, indices }
+    }
+}
+
+// Bounds of tile `i` of `n` in normalized device coordinates.
+fn tile(i: usize, n: usize) -> (Real, Real) {
+    let size = 2.0 / n as Real;
+    (-1.0 + size * i as Real, -1.0 + size * (i + 1) as Real)
+}
+
+fn to_view(light: &LightVolume, view: &Isometry) -> LightVolume {
+    match light {
+        LightVolume::Point(s) => LightVolume::Point(Sphere(s.0, view * s.1)),
+        LightVolume::Spot(s) => LightVolume::Spot(SpotLight {
+            position: view * s.position,
+            direction: view * s.direction,
+            ..*s
+        }),
+    }
+}
+
+fn light_and_froxel(light: &LightVolume, froxel: &Aabb) -> bool {
+    match light {
+        LightVolume::Point(s) => compute_sphere_and_aabb(s, froxel),
+        LightVolume::Spot(s) => {
+            let p = s.position.coords;
+            let range = Sphere(Ball::new(s.range), Isometry::translation(p.x, p.y, p.z));
+            compute_sphere_and_aabb(&range, froxel) && spot_and_sphere(s, froxel)
+        }
+    }
+}
+
+// Cone against the bounding sphere of the froxel, conservative near the cone edges.
+fn spot_and_sphere(spot: &SpotLight, froxel: &Aabb) -> bool {
+    let dir = match spot.direction.try_normalize(Real::EPSILON) {
+        Some(dir) => dir,
+        None => return false,
+    };
+    let radius = froxel.half_extents().norm();
+    let v = froxel.center() - spot.position;
+    let along = v.dot(&dir);
+    let across = (v.norm_squared() - along * along).max(0.0).sqrt();
+    let (sin, cos) = spot.angle.sin_cos();
+
+    across * cos - along * sin <= radius && along <= radius + spot.range && along >= -radius
+}
+
+// Light lists of every froxel packed in one buffer, ready for upload.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClusterLights {
+    // `offsets[i]..offsets[i + 1]` is the range of froxel `i` in `indices`.
+    pub offsets: Vec<u32>,
+    pub indices: Vec<u32>,
+}
+
+impl ClusterLights {
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn lights(&self, froxel: usize) -> &[u32] {
+        &self.indices[self.offsets[froxel] as usize..self.offsets[froxel + 1] as usize]
+    }
+}
+
+#[cfg(test)]
+fn test_grid() -> FroxelGrid {
+    let view = Isometry::look_at_rh(
+        &Point3::new(0.0, 0.0, 10.0),
+        &Point3::origin(),
+        &Vector3::y(),
+    );
+    FroxelGrid::new(
+        view,
+        std::f64::consts::FRAC_PI_2 as Real,
+        2.0,
+        0.5,
+        100.0,
+        [8, 4, 16],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_froxel_grid() {
+    let grid = test_grid();
+    assert_eq!(grid.len(), 8 * 4 * 16);
+
+    // Froxels tile the frustum without gaps.
+    assert_eq!(
+        grid.froxel(0).mins().z,
+        grid.froxel(grid.index(0, 0, 1)).maxs().z
+    );
+    assert_eq!(grid.froxel(0).maxs().z, -0.5);
+    assert!(grid
+        .froxel(grid.len() - 1)
+        .mins()
+        .z
+        .approx_eq(&-100.0, 1.0e-3));
+
+    for index in [0, 17, 100, grid.len() - 1] {
+        let center = grid.view.inverse() * grid.froxel(index).center();
+        assert_eq!(grid.froxel_at(&center), Some(index));
+    }
+    assert_eq!(grid.froxel_at(&Point3::new(0.0, 0.0, 20.0)), None);
+    assert_eq!(grid.froxel_at(&Point3::new(100.0, 0.0, 0.0)), None);
+    // Half width 20 and half height 10 at this depth.
+    assert_eq!(grid.froxel_at(&Point3::new(22.0, 0.0, 0.0)), None);
+    assert_eq!(grid.froxel_at(&Point3::new(-22.0, 0.0, 0.0)), None);
+    assert_eq!(grid.froxel_at(&Point3::new(0.0, 12.0, 0.0)), None);
+    assert_eq!(grid.froxel_at(&Point3::new(0.0, -12.0, 0.0)), None);
+    let edge = grid.froxel_at(&Point3::new(19.99, 9.99, 0.0)).unwrap();
+    assert_eq!(edge % (8 * 4), grid.index(7, 3, 0));
+
+    assert!(FroxelGrid::new(grid.view, 1.0, 1.0, 0.0, 100.0, [1, 1, 1]).is_none());
+    assert!(FroxelGrid::new(grid.view, 1.0, 1.0, 0.5, 100.0, [1, 0, 1]).is_none());
+}
+
+#[test]
+fn test_assign_lights() {
+    let grid = test_grid();
+    let lights = [
+        LightVolume::Point(Sphere(Ball::new(1.0), Isometry::translation(0.0, 0.0, 0.0))),
+        LightVolume::Spot(SpotLight {
+            position: Point3::new(0.0, 0.0, 0.0),
+            direction: Vector3::x(),
+            range: 20.0,
+            angle: 0.3,
+        }),
+        LightVolume::Point(Sphere(
+            Ball::new(1.0),
+            Isometry::translation(0.0, 0.0, 50.0),
+        )),
+    ];
+    let clusters = grid.assign(&lights);
+    assert_eq!(clusters.len(), grid.len());
+
+    let at = |x: Real, y: Real, z: Real| grid.froxel_at(&Point3::new(x, y, z)).unwrap();
+    assert_eq!(clusters.lights(at(0.0, 0.0, 0.0)), &[0, 1]);
+    assert_eq!(clusters.lights(at(6.0, 0.0, 0.0)), &[1]);
+    assert_eq!(clusters.lights(at(-8.0, 0.0, 0.0)), &[] as &[u32]);
+    assert_eq!(clusters.lights(at(2.0, -9.0, 0.0)), &[] as &[u32]);
+
+    // Every froxel holding part of a light lists it.
+    for (i, light) in lights.iter().enumerate() {
+        for p in [Point3::new(0.5, 0.5, 0.5), Point3::new(10.0, 0.5, 0.0)] {
+            let inside = match light {
+                LightVolume::Point(s) => crate::compute_point_and_sphere(&p, s),
+                LightVolume::Spot(s) => {
+                    let v = p - s.position;
+                    v.norm() <= s.range && v.normalize().dot(&s.direction) >= s.angle.cos()
+                }
+            };
+            if inside {
+                let froxel = grid.froxel_at(&p).unwrap();
+                assert!(clusters.lights(froxel).contains(&(i as u32)));
+            }
+        }
+    }
+}
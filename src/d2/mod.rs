@@ -0,0 +1,288 @@
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod shape;
+
+use std::fmt::Debug;
+
+use parry2d::shape::Shape;
+use shape::{
+    ApproxEq, Circle, Isometry2, Obb, Point2, Polygon, Ray, Real, Rect, Segment, Triangle,
+};
+
+#[allow(unused_imports)]
+use shape::{Ball, MSegment, MTriangle, Vector2};
+
+#[derive(Debug, Clone)]
+pub enum Geomery {
+    Point(Point2),
+    Ray(Ray),
+    Circle(Circle),
+    Rect(Rect),
+    Obb(Obb),
+    Triangle(Triangle),
+    Polygon(Polygon),
+    Segment(Segment),
+}
+
+impl PartialEq for Geomery {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Geomery::Point(a), Geomery::Point(b)) => a == b,
+            (Geomery::Ray(a), Geomery::Ray(b)) => a.origin == b.origin && a.dir == b.dir,
+            (Geomery::Circle(a), Geomery::Circle(b)) => a == b,
+            (Geomery::Rect(a), Geomery::Rect(b)) => a == b,
+            (Geomery::Obb(a), Geomery::Obb(b)) => a == b,
+            (Geomery::Triangle(a), Geomery::Triangle(b)) => a == b,
+            (Geomery::Polygon(a), Geomery::Polygon(b)) => a == b,
+            (Geomery::Segment(a), Geomery::Segment(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl ApproxEq<Real> for Geomery {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        match (self, other) {
+            (Geomery::Point(a), Geomery::Point(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Ray(a), Geomery::Ray(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Circle(a), Geomery::Circle(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Rect(a), Geomery::Rect(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Obb(a), Geomery::Obb(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Triangle(a), Geomery::Triangle(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Polygon(a), Geomery::Polygon(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Segment(a), Geomery::Segment(b)) => a.approx_eq(b, epsilon),
+            _ => false,
+        }
+    }
+}
+
+impl Geomery {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Geomery::Point(_) => "Point",
+            Geomery::Ray(_) => "Ray",
+            Geomery::Circle(_) => "Circle",
+            Geomery::Rect(_) => "Rect",
+            Geomery::Obb(_) => "Obb",
+            Geomery::Triangle(_) => "Triangle",
+            Geomery::Polygon(_) => "Polygon",
+            Geomery::Segment(_) => "Segment",
+        }
+    }
+}
+
+#[test]
+fn test_geomery_eq() {
+    let polygon = Geomery::Polygon(
+        Polygon::new(&[
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(2.0, 2.0),
+        ])
+        .unwrap(),
+    );
+    assert_eq!(polygon.clone(), polygon);
+    assert_eq!(polygon.kind(), "Polygon");
+
+    let rect = Geomery::Rect(Rect::from_xywh(0.0, 0.0, 10.0, 10.0));
+    assert_ne!(rect, polygon);
+    assert!(format!("{:?}", rect).contains("5.0, 5.0"));
+
+    let moved = Geomery::Rect(Rect::from_xywh(1.0e-4, 0.0, 10.0, 10.0));
+    assert_ne!(moved, rect);
+    assert_eq!(moved.approx_eq(&rect, 1.0e-3), true);
+    assert_eq!(moved.approx_eq(&rect, 1.0e-5), false);
+}
+
+fn as_shape(geomery: &Geomery) -> Option<(&Isometry2, &dyn Shape)> {
+    match geomery {
+        Geomery::Circle(c) => Some((&c.1, &c.0)),
+        Geomery::Rect(r) => Some((&r.1, &r.0)),
+        Geomery::Obb(obb) => Some((&obb.1, &obb.0)),
+        Geomery::Triangle(t) => Some((&t.1, &t.0)),
+        Geomery::Polygon(p) => Some((&p.1, &p.0)),
+        Geomery::Segment(s) => Some((&s.1, &s.0)),
+        Geomery::Point(_) | Geomery::Ray(_) => None,
+    }
+}
+
+pub fn test_collision(geomery1: &Geomery, geomery2: &Geomery) -> bool {
+    let result = match (geomery1, geomery2) {
+        (Geomery::Point(p), other) | (other, Geomery::Point(p)) => {
+            as_shape(other).map(|(m, s)| s.contains_point(m, p))
+        }
+        (Geomery::Ray(r), other) | (other, Geomery::Ray(r)) => {
+            as_shape(other).map(|(m, s)| s.intersects_ray(m, r, Real::MAX))
+        }
+        _ => match (as_shape(geomery1), as_shape(geomery2)) {
+            (Some((m1, s1)), Some((m2, s2))) => {
+                parry2d::query::intersection_test(m1, s1, m2, s2).ok()
+            }
+            _ => None,
+        },
+    };
+
+    match result {
+        Some(r) => r,
+        None => panic!(
+            "not support geomery type test collision!!! g1: {:?}, g2: {:?}",
+            geomery1, geomery2
+        ),
+    }
+}
+
+pub fn raycast(ray: &Ray, geomery: &Geomery, max_toi: Real) -> Option<Real> {
+    as_shape(geomery).and_then(|(m, s)| s.cast_ray(m, ray, max_toi, true))
+}
+
+pub fn contains_point(geomery: &Geomery, point: &Point2) -> bool {
+    match geomery {
+        Geomery::Point(p) => p == point,
+        _ => as_shape(geomery).is_some_and(|(m, s)| s.contains_point(m, point)),
+    }
+}
+
+// Geomeries are in draw order, the last one is the topmost.
+pub fn hit_test(point: &Point2, geomeries: &[Geomery]) -> Option<usize> {
+    geomeries.iter().rposition(|g| contains_point(g, point))
+}
+
+pub fn hit_test_all(point: &Point2, geomeries: &[Geomery]) -> Vec<usize> {
+    (0..geomeries.len())
+        .rev()
+        .filter(|i| contains_point(&geomeries[*i], point))
+        .collect()
+}
+
+#[test]
+fn test_point_and_circle() {
+    let point = Geomery::Point(Point2::new(0.0, 0.0));
+
+    let circle = Geomery::Circle(Circle(Ball::new(2.0), Isometry2::translation(0.0, 2.0)));
+    assert_eq!(test_collision(&point, &circle), true);
+    assert_eq!(test_collision(&circle, &point), true);
+
+    let circle = Geomery::Circle(Circle(Ball::new(2.0), Isometry2::translation(2.0, 2.0)));
+    assert_eq!(test_collision(&point, &circle), false);
+}
+
+#[test]
+fn test_point_and_rect() {
+    let point = Geomery::Point(Point2::new(0.0, 0.0));
+
+    let rect = Geomery::Rect(Rect::new(Point2::new(-1.0, -1.0), Point2::new(1.0, 1.0)));
+    assert_eq!(test_collision(&point, &rect), true);
+
+    let rect = Geomery::Rect(Rect::from_xywh(0.0, 1.0, 2.0, 2.0));
+    assert_eq!(test_collision(&point, &rect), false);
+}
+
+#[test]
+fn test_ray_and_triangle() {
+    let ray = Geomery::Ray(Ray::new(Point2::new(0.0, 0.0), Vector2::new(1.0, 1.0)));
+
+    let tri = Geomery::Triangle(Triangle(
+        MTriangle::new(
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(0.0, 1.0),
+        ),
+        Isometry2::translation(1.0, 1.0),
+    ));
+    assert_eq!(test_collision(&ray, &tri), true);
+
+    let tri = Geomery::Triangle(Triangle(
+        MTriangle::new(
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(0.0, 1.0),
+        ),
+        Isometry2::translation(1.0, -2.0),
+    ));
+    assert_eq!(test_collision(&tri, &ray), false);
+}
+
+#[test]
+fn test_rect_and_obb() {
+    let rect = Geomery::Rect(Rect::new(Point2::new(-1.0, -1.0), Point2::new(1.0, 1.0)));
+
+    let obb = Geomery::Obb(Obb::new(
+        Point2::new(2.3, 0.0),
+        std::f32::consts::FRAC_PI_4,
+        Vector2::new(1.0, 1.0),
+    ));
+    assert_eq!(test_collision(&rect, &obb), true);
+
+    let obb = Geomery::Obb(Obb::new(Point2::new(2.3, 0.0), 0.0, Vector2::new(1.0, 1.0)));
+    assert_eq!(test_collision(&rect, &obb), false);
+}
+
+#[test]
+fn test_polygon_and_segment() {
+    let polygon = Geomery::Polygon(
+        Polygon::new(&[
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(1.0, 3.0),
+            Point2::new(0.0, 2.0),
+        ])
+        .unwrap(),
+    );
+
+    let segment = Geomery::Segment(Segment(
+        MSegment::new(Point2::new(-1.0, 1.0), Point2::new(3.0, 1.0)),
+        Isometry2::identity(),
+    ));
+    assert_eq!(test_collision(&polygon, &segment), true);
+
+    let segment = Geomery::Segment(Segment(
+        MSegment::new(Point2::new(-1.0, 1.0), Point2::new(3.0, 1.0)),
+        Isometry2::translation(0.0, 3.0),
+    ));
+    assert_eq!(test_collision(&segment, &polygon), false);
+}
+
+#[test]
+fn test_circle_and_circle() {
+    let circle0 = Geomery::Circle(Circle(Ball::new(1.0), Isometry2::identity()));
+
+    let circle = Geomery::Circle(Circle(Ball::new(1.0), Isometry2::translation(2.0, 0.0)));
+    assert_eq!(test_collision(&circle0, &circle), true);
+
+    let circle = Geomery::Circle(Circle(Ball::new(1.0), Isometry2::translation(2.0, 2.0)));
+    assert_eq!(test_collision(&circle0, &circle), false);
+}
+
+#[test]
+#[should_panic]
+fn test_point_and_ray() {
+    let point = Geomery::Point(Point2::new(0.0, 0.0));
+    let ray = Geomery::Ray(Ray::new(Point2::new(0.0, 0.0), Vector2::new(1.0, 1.0)));
+    test_collision(&point, &ray);
+}
+
+#[test]
+fn test_raycast() {
+    let ray = Ray::new(Point2::new(0.0, 0.0), Vector2::new(1.0, 0.0));
+
+    let circle = Geomery::Circle(Circle(Ball::new(1.0), Isometry2::translation(3.0, 0.0)));
+    assert_eq!(raycast(&ray, &circle, Real::MAX), Some(2.0));
+    assert_eq!(raycast(&ray, &circle, 1.0), None);
+}
+
+#[test]
+fn test_hit_test() {
+    let layout = vec![
+        Geomery::Rect(Rect::from_xywh(0.0, 0.0, 100.0, 100.0)),
+        Geomery::Rect(Rect::from_xywh(10.0, 10.0, 20.0, 20.0)),
+        Geomery::Circle(Circle(Ball::new(5.0), Isometry2::translation(50.0, 50.0))),
+    ];
+
+    assert_eq!(hit_test(&Point2::new(15.0, 15.0), &layout), Some(1));
+    assert_eq!(hit_test(&Point2::new(50.0, 52.0), &layout), Some(2));
+    assert_eq!(hit_test(&Point2::new(80.0, 20.0), &layout), Some(0));
+    assert_eq!(hit_test(&Point2::new(150.0, 20.0), &layout), None);
+
+    assert_eq!(hit_test_all(&Point2::new(15.0, 15.0), &layout), vec![1, 0]);
+}
@@ -0,0 +1,285 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::shape::{
+    Ball, Circle, ConvexPolygon, Cuboid, Isometry2, MSegment, MTriangle, Obb, Point2, Polygon, Ray,
+    Real, Rect, Segment, Triangle, Vector2,
+};
+use super::Geomery;
+use crate::serialize::impl_serde;
+
+use parry2d::na::{Complex, Translation2, UnitComplex};
+
+type Array2 = [Real; 2];
+
+fn to_array(v: &Vector2) -> Array2 {
+    [v.x, v.y]
+}
+
+fn to_vector(a: &Array2) -> Vector2 {
+    Vector2::new(a[0], a[1])
+}
+
+fn to_point(a: &Array2) -> Point2 {
+    Point2::new(a[0], a[1])
+}
+
+// Rotation is stored as unit complex `[cos, sin]`.
+#[derive(Serialize, Deserialize)]
+struct IsometryRepr {
+    translation: Array2,
+    rotation: Array2,
+}
+
+impl From<&Isometry2> for IsometryRepr {
+    fn from(iso: &Isometry2) -> Self {
+        Self {
+            translation: to_array(&iso.translation.vector),
+            rotation: [iso.rotation.re, iso.rotation.im],
+        }
+    }
+}
+
+impl From<&IsometryRepr> for Isometry2 {
+    fn from(repr: &IsometryRepr) -> Self {
+        let c = Complex::new(repr.rotation[0], repr.rotation[1]);
+        let rotation = if (c.norm_sqr() - 1.0).abs() <= Real::EPSILON * 4.0 {
+            UnitComplex::new_unchecked(c)
+        } else {
+            UnitComplex::from_complex(c)
+        };
+
+        Isometry2::from_parts(Translation2::from(to_vector(&repr.translation)), rotation)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RayRepr {
+    origin: Array2,
+    dir: Array2,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CircleRepr {
+    radius: Real,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RectRepr {
+    mins: Array2,
+    maxs: Array2,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ObbRepr {
+    half_extents: Array2,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TriangleRepr {
+    a: Array2,
+    b: Array2,
+    c: Array2,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PolygonRepr {
+    points: Vec<Array2>,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SegmentRepr {
+    a: Array2,
+    b: Array2,
+    isometry: IsometryRepr,
+}
+
+impl_serde!(
+    Circle,
+    CircleRepr,
+    |s| CircleRepr {
+        radius: s.0.radius,
+        isometry: (&s.1).into(),
+    },
+    |r| Ok(Circle(Ball::new(r.radius), (&r.isometry).into()))
+);
+
+impl_serde!(
+    Rect,
+    RectRepr,
+    |s| RectRepr {
+        mins: to_array(&s.2.mins.coords),
+        maxs: to_array(&s.2.maxs.coords),
+    },
+    |r| Ok(Rect::new(to_point(&r.mins), to_point(&r.maxs)))
+);
+
+impl_serde!(
+    Obb,
+    ObbRepr,
+    |s| ObbRepr {
+        half_extents: to_array(&s.0.half_extents),
+        isometry: (&s.1).into(),
+    },
+    |r| Ok(Obb(
+        Cuboid::new(to_vector(&r.half_extents)),
+        (&r.isometry).into()
+    ))
+);
+
+impl_serde!(
+    Triangle,
+    TriangleRepr,
+    |s| TriangleRepr {
+        a: to_array(&s.0.a.coords),
+        b: to_array(&s.0.b.coords),
+        c: to_array(&s.0.c.coords),
+        isometry: (&s.1).into(),
+    },
+    |r| Ok(Triangle(
+        MTriangle::new(to_point(&r.a), to_point(&r.b), to_point(&r.c)),
+        (&r.isometry).into()
+    ))
+);
+
+impl_serde!(
+    Polygon,
+    PolygonRepr,
+    |s| PolygonRepr {
+        points: s.0.points().iter().map(|p| to_array(&p.coords)).collect(),
+        isometry: (&s.1).into(),
+    },
+    |r| {
+        let points = r.points.iter().map(to_point).collect();
+        let polygon = ConvexPolygon::from_convex_polyline(points)
+            .ok_or_else(|| D::Error::custom("invalid convex polygon points"))?;
+        Ok(Polygon(polygon, (&r.isometry).into()))
+    }
+);
+
+impl_serde!(
+    Segment,
+    SegmentRepr,
+    |s| SegmentRepr {
+        a: to_array(&s.0.a.coords),
+        b: to_array(&s.0.b.coords),
+        isometry: (&s.1).into(),
+    },
+    |r| Ok(Segment(
+        MSegment::new(to_point(&r.a), to_point(&r.b)),
+        (&r.isometry).into()
+    ))
+);
+
+#[derive(Serialize)]
+#[serde(rename = "Geomery")]
+enum GeomeryRef<'a> {
+    Point(Array2),
+    Ray(RayRepr),
+    Circle(&'a Circle),
+    Rect(&'a Rect),
+    Obb(&'a Obb),
+    Triangle(&'a Triangle),
+    Polygon(&'a Polygon),
+    Segment(&'a Segment),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Geomery")]
+enum GeomeryOwned {
+    Point(Array2),
+    Ray(RayRepr),
+    Circle(Circle),
+    Rect(Rect),
+    Obb(Obb),
+    Triangle(Triangle),
+    Polygon(Polygon),
+    Segment(Segment),
+}
+
+impl Serialize for Geomery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            Geomery::Point(p) => GeomeryRef::Point(to_array(&p.coords)),
+            Geomery::Ray(r) => GeomeryRef::Ray(RayRepr {
+                origin: to_array(&r.origin.coords),
+                dir: to_array(&r.dir),
+            }),
+            Geomery::Circle(c) => GeomeryRef::Circle(c),
+            Geomery::Rect(r) => GeomeryRef::Rect(r),
+            Geomery::Obb(obb) => GeomeryRef::Obb(obb),
+            Geomery::Triangle(t) => GeomeryRef::Triangle(t),
+            Geomery::Polygon(p) => GeomeryRef::Polygon(p),
+            Geomery::Segment(s) => GeomeryRef::Segment(s),
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Geomery {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match GeomeryOwned::deserialize(deserializer)? {
+            GeomeryOwned::Point(p) => Geomery::Point(to_point(&p)),
+            GeomeryOwned::Ray(r) => Geomery::Ray(Ray::new(to_point(&r.origin), to_vector(&r.dir))),
+            GeomeryOwned::Circle(c) => Geomery::Circle(c),
+            GeomeryOwned::Rect(r) => Geomery::Rect(r),
+            GeomeryOwned::Obb(obb) => Geomery::Obb(obb),
+            GeomeryOwned::Triangle(t) => Geomery::Triangle(t),
+            GeomeryOwned::Polygon(p) => Geomery::Polygon(p),
+            GeomeryOwned::Segment(s) => Geomery::Segment(s),
+        })
+    }
+}
+
+#[cfg(test)]
+fn test_geomeries() -> Vec<Geomery> {
+    vec![
+        Geomery::Point(Point2::new(1.0, 2.0)),
+        Geomery::Ray(Ray::new(Point2::new(0.0, 0.0), Vector2::new(1.0, 1.0))),
+        Geomery::Circle(Circle(Ball::new(1.5), Isometry2::translation(1.0, 2.0))),
+        Geomery::Rect(Rect::from_xywh(10.0, 20.0, 30.0, 40.0)),
+        Geomery::Obb(Obb::new(Point2::new(1.0, 1.0), 0.3, Vector2::new(1.0, 2.0))),
+        Geomery::Triangle(Triangle(
+            MTriangle::new(
+                Point2::new(0.0, 0.0),
+                Point2::new(1.0, 0.0),
+                Point2::new(0.0, 1.0),
+            ),
+            Isometry2::new(Vector2::new(1.0, 0.0), 1.0),
+        )),
+        Geomery::Polygon(
+            Polygon::new(&[
+                Point2::new(0.0, 0.0),
+                Point2::new(2.0, 0.0),
+                Point2::new(2.0, 2.0),
+                Point2::new(0.0, 2.0),
+            ])
+            .unwrap(),
+        ),
+        Geomery::Segment(Segment(
+            MSegment::new(Point2::new(-1.0, 1.0), Point2::new(3.0, 1.0)),
+            Isometry2::identity(),
+        )),
+    ]
+}
+
+#[test]
+fn test_serde_json_round_trip() {
+    for geomery in test_geomeries() {
+        let json = serde_json::to_string(&geomery).unwrap();
+        let back: Geomery = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json, "{:?}", geomery);
+    }
+}
+
+#[test]
+fn test_serde_bincode_round_trip() {
+    for geomery in test_geomeries() {
+        let bytes = bincode::serialize(&geomery).unwrap();
+        let back: Geomery = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(bincode::serialize(&back).unwrap(), bytes, "{:?}", geomery);
+    }
+}
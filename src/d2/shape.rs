@@ -0,0 +1,151 @@
+use parry2d::{
+    bounding_volume::aabb::Aabb as AABB,
+    math::{Isometry as Isometry2D, Point, Vector},
+    query::Ray as Ray2D,
+    shape::{
+        Ball as Ball2D, ConvexPolygon as ConvexPolygon2D, Cuboid as Cuboid2D, Segment as Segment2D,
+        Triangle as Triangle2D,
+    },
+};
+
+pub type Real = parry2d::math::Real;
+pub type Point2 = Point<Real>;
+pub type Vector2 = Vector<Real>;
+pub type Isometry2 = Isometry2D<Real>;
+pub type ConvexPolygon = ConvexPolygon2D;
+pub type Ball = Ball2D;
+pub type Cuboid = Cuboid2D;
+pub type MTriangle = Triangle2D;
+pub type MSegment = Segment2D;
+pub type Ray = Ray2D;
+
+pub use crate::shape::ApproxEq;
+
+impl ApproxEq<Real> for Vector2 {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        (self - other).amax() <= epsilon
+    }
+}
+
+impl ApproxEq<Real> for Point2 {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.coords.approx_eq(&other.coords, epsilon)
+    }
+}
+
+impl ApproxEq<Real> for Isometry2 {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.translation
+            .vector
+            .approx_eq(&other.translation.vector, epsilon)
+            && self.rotation.angle_to(&other.rotation).abs() <= epsilon
+    }
+}
+
+impl ApproxEq<Real> for Ray {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.origin.approx_eq(&other.origin, epsilon) && self.dir.approx_eq(&other.dir, epsilon)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle(pub Ball, pub Isometry2);
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangle(pub MTriangle, pub Isometry2);
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment(pub MSegment, pub Isometry2);
+#[derive(Debug, Clone)]
+pub struct Polygon(pub ConvexPolygon, pub Isometry2);
+
+// `ConvexPolygon` has no `PartialEq`, polygons are compared by their points.
+impl PartialEq for Polygon {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.points() == other.0.points() && self.1 == other.1
+    }
+}
+
+impl Polygon {
+    pub fn new(points: &[Point2]) -> Option<Self> {
+        ConvexPolygon::from_convex_hull(points).map(|polygon| Self(polygon, Isometry2::identity()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rect(pub Cuboid, pub Isometry2, pub AABB);
+
+impl Rect {
+    pub fn new(mins: Point2, maxs: Point2) -> Self {
+        let r = (maxs - mins) * 0.5;
+        let cuboid = Cuboid::new(r);
+        let pos = Isometry2::translation(mins.x + r.x, mins.y + r.y);
+
+        let aabb = AABB::new(mins, maxs);
+
+        Self(cuboid, pos, aabb)
+    }
+
+    pub fn from_xywh(x: Real, y: Real, width: Real, height: Real) -> Self {
+        Self::new(Point2::new(x, y), Point2::new(x + width, y + height))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Obb(pub Cuboid, pub Isometry2);
+
+impl Obb {
+    pub fn new(pos: Point2, angle: Real, half_extents: Vector2) -> Self {
+        let cuboid = Cuboid::new(half_extents);
+
+        Self(cuboid, Isometry2::new(Vector2::new(pos.x, pos.y), angle))
+    }
+}
+
+impl ApproxEq<Real> for Circle {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        (self.0.radius - other.0.radius).abs() <= epsilon && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq<Real> for Triangle {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0.a.approx_eq(&other.0.a, epsilon)
+            && self.0.b.approx_eq(&other.0.b, epsilon)
+            && self.0.c.approx_eq(&other.0.c, epsilon)
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq<Real> for Segment {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0.a.approx_eq(&other.0.a, epsilon)
+            && self.0.b.approx_eq(&other.0.b, epsilon)
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq<Real> for Polygon {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        let (p1, p2) = (self.0.points(), other.0.points());
+        p1.len() == p2.len()
+            && self.1.approx_eq(&other.1, epsilon)
+            && p1
+                .iter()
+                .all(|a| p2.iter().any(|b| a.approx_eq(b, epsilon)))
+    }
+}
+
+impl ApproxEq<Real> for Rect {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.2.mins.approx_eq(&other.2.mins, epsilon)
+            && self.2.maxs.approx_eq(&other.2.maxs, epsilon)
+    }
+}
+
+impl ApproxEq<Real> for Obb {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0
+            .half_extents
+            .approx_eq(&other.0.half_extents, epsilon)
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
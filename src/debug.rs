@@ -0,0 +1,282 @@
+use crate::shape::{
+    solid_convex_hull, Aabb, Ball, ConvexPolyhedron, Custom, Isometry, Point3, Real, Vector3,
+};
+use crate::Geomery;
+
+use parry3d::na::Unit;
+
+// Subdivisions of sphere circles.
+pub const DEBUG_SEGMENTS: u32 = 24;
+// Rays and planes are infinite, they are drawn clipped to these sizes.
+pub const DEBUG_RAY_LENGTH: Real = 100.0;
+pub const DEBUG_PLANE_SIZE: Real = 10.0;
+pub const DEBUG_POINT_SIZE: Real = 0.1;
+
+pub type Line = (Point3, Point3);
+pub type Face = [Point3; 3];
+
+fn indexed_lines(m: &Isometry, points: &[Point3], indices: &[[u32; 2]]) -> Vec<Line> {
+    indices
+        .iter()
+        .map(|[a, b]| (m * points[*a as usize], m * points[*b as usize]))
+        .collect()
+}
+
+fn indexed_faces(m: &Isometry, points: &[Point3], indices: &[[u32; 3]]) -> Vec<Face> {
+    indices
+        .iter()
+        .map(|idx| idx.map(|i| m * points[i as usize]))
+        .collect()
+}
+
+// Corners of a square patch of the plane, counter clockwise around the normal.
+fn plane_patch(normal: &Unit<Vector3>, m: &Isometry) -> [Point3; 4] {
+    let axis = if normal.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = normal.cross(&axis).normalize() * DEBUG_PLANE_SIZE;
+    let v = normal.cross(&u);
+
+    [u + v, -u + v, -u - v, u - v].map(|p| m * Point3::from(p))
+}
+
+fn polyhedron_edges(polyhedron: &ConvexPolyhedron, m: &Isometry) -> Vec<Line> {
+    // Hull edges between coplanar triangles are kept by parry but not
+    // referenced by the merged faces.
+    let adj = polyhedron.edges_adj_to_face();
+    let mut ids: Vec<u32> = polyhedron
+        .faces()
+        .iter()
+        .flat_map(|face| {
+            let start = face.first_vertex_or_edge as usize;
+            adj[start..start + face.num_vertices_or_edges as usize].iter()
+        })
+        .copied()
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let (points, edges) = (polyhedron.points(), polyhedron.edges());
+    ids.iter()
+        .map(|id| {
+            let e = &edges[*id as usize];
+            (
+                m * points[e.vertices.x as usize],
+                m * points[e.vertices.y as usize],
+            )
+        })
+        .collect()
+}
+
+// Support points sampled on a sphere of directions.
+fn custom_points(custom: &Custom) -> Vec<Point3> {
+    let (points, _) = Ball::new(1.0).to_trimesh(DEBUG_SEGMENTS, DEBUG_SEGMENTS / 2);
+    points
+        .iter()
+        .map(|p| custom.0 .0.local_support_point(&p.coords))
+        .collect()
+}
+
+// None when the samples are flat or coincident.
+fn custom_hull(custom: &Custom) -> Option<ConvexPolyhedron> {
+    solid_convex_hull(&custom_points(custom))
+}
+
+pub fn debug_lines(geomery: &Geomery) -> Vec<Line> {
+    match geomery {
+        Geomery::Point(p) => [Vector3::x(), Vector3::y(), Vector3::z()]
+            .iter()
+            .map(|axis| {
+                let d = axis * DEBUG_POINT_SIZE;
+                (p - d, p + d)
+            })
+            .collect(),
+        Geomery::Ray(r) => {
+            let dir = r
+                .dir
+                .try_normalize(Real::EPSILON)
+                .unwrap_or_else(Vector3::zeros);
+            vec![(r.origin, r.origin + dir * DEBUG_RAY_LENGTH)]
+        }
+        Geomery::Plane(p) => {
+            let c = plane_patch(&p.0.normal, &p.1);
+            let center = p.1 * Point3::origin();
+            vec![
+                (c[0], c[1]),
+                (c[1], c[2]),
+                (c[2], c[3]),
+                (c[3], c[0]),
+                (center, center + p.1 * p.0.normal.into_inner()),
+            ]
+        }
+        Geomery::Sphere(s) => {
+            let (points, indices) = s.0.to_outline(DEBUG_SEGMENTS);
+            indexed_lines(&s.1, &points, &indices)
+        }
+        Geomery::Ball(b) => {
+            let (points, indices) = b.to_outline(DEBUG_SEGMENTS);
+            indexed_lines(&Isometry::identity(), &points, &indices)
+        }
+        Geomery::Aabb(ab) => {
+            let (points, indices) = ab.0.to_outline();
+            indexed_lines(&ab.1, &points, &indices)
+        }
+        Geomery::Obb(obb) => {
+            let (points, indices) = obb.0.to_outline();
+            indexed_lines(&obb.1, &points, &indices)
+        }
+        Geomery::Triangle(t) => {
+            let [a, b, c] = t.0.vertices().map(|p| t.1 * p);
+            vec![(a, b), (b, c), (c, a)]
+        }
+        Geomery::Frustum(f) => polyhedron_edges(&f.0, &f.1),
+        Geomery::ConvexHull(c) => polyhedron_edges(&c.0, &c.1),
+        Geomery::TriMesh(t) => {
+            t.0.triangles()
+                .flat_map(|tri| {
+                    let [a, b, c] = tri.vertices().map(|p| t.1 * p);
+                    [(a, b), (b, c), (c, a)]
+                })
+                .collect()
+        }
+        Geomery::Compound(parts) => parts.iter().flat_map(debug_lines).collect(),
+        // Degenerate shapes are drawn as the box around their samples.
+        Geomery::Custom(c) => match custom_hull(c) {
+            Some(hull) => polyhedron_edges(&hull, &c.1),
+            None => Aabb::from_points(&custom_points(c))
+                .map(|ab| {
+                    let (points, indices) = ab.0.to_outline();
+                    indexed_lines(&(c.1 * ab.1), &points, &indices)
+                })
+                .unwrap_or_default(),
+        },
+    }
+}
+
+// Points and rays have no surface and give no faces.
+pub fn debug_triangles(geomery: &Geomery) -> Vec<Face> {
+    match geomery {
+        Geomery::Point(_) | Geomery::Ray(_) => Vec::new(),
+        Geomery::Plane(p) => {
+            let c = plane_patch(&p.0.normal, &p.1);
+            vec![[c[0], c[1], c[2]], [c[0], c[2], c[3]]]
+        }
+        Geomery::Sphere(s) => {
+            let (points, indices) = s.0.to_trimesh(DEBUG_SEGMENTS, DEBUG_SEGMENTS / 2);
+            indexed_faces(&s.1, &points, &indices)
+        }
+        Geomery::Ball(b) => {
+            let (points, indices) = b.to_trimesh(DEBUG_SEGMENTS, DEBUG_SEGMENTS / 2);
+            indexed_faces(&Isometry::identity(), &points, &indices)
+        }
+        Geomery::Aabb(ab) => {
+            let (points, indices) = ab.0.to_trimesh();
+            indexed_faces(&ab.1, &points, &indices)
+        }
+        Geomery::Obb(obb) => {
+            let (points, indices) = obb.0.to_trimesh();
+            indexed_faces(&obb.1, &points, &indices)
+        }
+        Geomery::Triangle(t) => vec![t.0.vertices().map(|p| t.1 * p)],
+        Geomery::Frustum(f) => {
+            let (points, indices) = f.0.to_trimesh();
+            indexed_faces(&f.1, &points, &indices)
+        }
+        Geomery::ConvexHull(c) => {
+            let (points, indices) = c.0.to_trimesh();
+            indexed_faces(&c.1, &points, &indices)
+        }
+        Geomery::TriMesh(t) => indexed_faces(&t.1, t.0.vertices(), t.0.indices()),
+        Geomery::Compound(parts) => parts.iter().flat_map(debug_triangles).collect(),
+        Geomery::Custom(c) => custom_hull(c)
+            .map(|hull| {
+                let (points, indices) = hull.to_trimesh();
+                indexed_faces(&c.1, &points, &indices)
+            })
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+use crate::shape::test_frustum;
+#[allow(unused_imports)]
+use crate::shape::{HalfSpace, Obb, Plane, Ray, Sphere};
+
+#[test]
+fn test_debug_lines() {
+    let aabb = Geomery::Aabb(Aabb::new(
+        Point3::new(1.0, 1.0, 1.0),
+        Point3::new(2.0, 3.0, 4.0),
+    ));
+    let lines = debug_lines(&aabb);
+    assert_eq!(lines.len(), 12);
+    for (a, b) in lines {
+        for p in [a, b] {
+            assert!([1.0, 2.0].contains(&p.x));
+            assert!([1.0, 3.0].contains(&p.y));
+            assert!([1.0, 4.0].contains(&p.z));
+        }
+    }
+
+    assert_eq!(debug_lines(&Geomery::Frustum(test_frustum())).len(), 12);
+
+    let center = Point3::new(1.0, 2.0, 3.0);
+    let sphere = Geomery::Sphere(Sphere(Ball::new(2.0), Isometry::translation(1.0, 2.0, 3.0)));
+    for (a, b) in debug_lines(&sphere) {
+        assert!(((a - center).norm() - 2.0).abs() < 1.0e-5);
+        assert!(((b - center).norm() - 2.0).abs() < 1.0e-5);
+    }
+
+    let ray = Geomery::Ray(Ray::new(Point3::origin(), Vector3::new(0.0, 2.0, 0.0)));
+    assert_eq!(
+        debug_lines(&ray),
+        vec![(Point3::origin(), Point3::new(0.0, DEBUG_RAY_LENGTH, 0.0))]
+    );
+}
+
+#[test]
+fn test_debug_plane() {
+    let plane = Geomery::Plane(Plane(
+        HalfSpace::new(Vector3::y_axis()),
+        Isometry::translation(0.0, 1.0, 0.0),
+    ));
+
+    let lines = debug_lines(&plane);
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[4],
+        (Point3::new(0.0, 1.0, 0.0), Point3::new(0.0, 2.0, 0.0))
+    );
+    for (a, _) in &lines[..4] {
+        assert_eq!(a.y, 1.0);
+    }
+
+    let faces = debug_triangles(&plane);
+    assert_eq!(faces.len(), 2);
+    for face in faces {
+        let n = (face[1] - face[0]).cross(&(face[2] - face[0]));
+        assert!(n.y > 0.0);
+    }
+}
+
+#[test]
+fn test_debug_triangles() {
+    let obb = Geomery::Obb(Obb::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::new(0.0, 0.5, 0.0),
+        Vector3::new(1.0, 1.0, 1.0),
+    ));
+    assert_eq!(debug_triangles(&obb).len(), 12);
+    assert_eq!(debug_triangles(&Geomery::Point(Point3::origin())).len(), 0);
+    assert_eq!(debug_triangles(&Geomery::Frustum(test_frustum())).len(), 12);
+
+    let ball = debug_triangles(&Geomery::Ball(Ball::new(1.0)));
+    assert!(!ball.is_empty());
+    for face in ball {
+        for p in face {
+            assert!((p.coords.norm() - 1.0).abs() < 1.0e-5);
+        }
+    }
+}
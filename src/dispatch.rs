@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{
+    compute_aabb_and_aabb, compute_aabb_and_frustum, compute_aabb_and_obb, compute_aabb_and_plane,
+    compute_aabb_and_triangle, compute_frustum_and_frustum, compute_obb_and_frustum,
+    compute_obb_and_obb, compute_obb_and_plane, compute_obb_and_triangle,
+    compute_plane_and_frustum, compute_plane_and_triangle, compute_point_and_aabb,
+    compute_point_and_obb, compute_point_and_sphere, compute_ray_and_aabb, compute_ray_and_obb,
+    compute_ray_and_sphere, compute_ray_and_triangle, compute_sphere_and_aabb,
+    compute_sphere_and_frustum, compute_sphere_and_obb, compute_sphere_and_plane,
+    compute_sphere_and_sphere, compute_sphere_and_triangle, compute_triangle_and_frustum,
+    compute_triangle_and_triangle,
+};
+use crate::{compute_compound, try_test_collision, Geomery, GeomeryKind};
+
+pub type Handler = Arc<dyn Fn(&Geomery, &Geomery) -> Option<bool> + Send + Sync>;
+
+// Collision handlers keyed by the `Geomery::kind` of both arguments.
+// Pairs without a handler go to the fallback, tried in both orders.
+pub struct Dispatcher {
+    handlers: HashMap<(GeomeryKind, GeomeryKind), Handler>,
+    fallback: Option<Handler>,
+}
+
+macro_rules! register {
+    ($dispatcher:ident, $kind1:ident, $kind2:ident, $f:path) => {
+        $dispatcher.register_symmetric(GeomeryKind::$kind1, GeomeryKind::$kind2, |g1, g2| {
+            match (g1, g2) {
+                (Geomery::$kind1(a), Geomery::$kind2(b)) => Some($f(a, b)),
+                _ => None,
+            }
+        })
+    };
+}
+
+// Handlers for every pair of `test_collision`, in both orders, falling back to
+// `try_test_collision` for meshes, hulls and custom shapes.
+impl Default for Dispatcher {
+    fn default() -> Self {
+        let mut d = Self::new();
+        register!(d, Point, Sphere, compute_point_and_sphere);
+        register!(d, Point, Aabb, compute_point_and_aabb);
+        register!(d, Point, Obb, compute_point_and_obb);
+        register!(d, Ray, Sphere, compute_ray_and_sphere);
+        register!(d, Ray, Aabb, compute_ray_and_aabb);
+        register!(d, Ray, Triangle, compute_ray_and_triangle);
+        register!(d, Ray, Obb, compute_ray_and_obb);
+        register!(d, Plane, Triangle, compute_plane_and_triangle);
+        register!(d, Sphere, Plane, compute_sphere_and_plane);
+        register!(d, Sphere, Sphere, compute_sphere_and_sphere);
+        register!(d, Sphere, Aabb, compute_sphere_and_aabb);
+        register!(d, Sphere, Triangle, compute_sphere_and_triangle);
+        register!(d, Sphere, Frustum, compute_sphere_and_frustum);
+        register!(d, Sphere, Obb, compute_sphere_and_obb);
+        register!(d, Aabb, Plane, compute_aabb_and_plane);
+        register!(d, Aabb, Aabb, compute_aabb_and_aabb);
+        register!(d, Aabb, Triangle, compute_aabb_and_triangle);
+        register!(d, Aabb, Frustum, compute_aabb_and_frustum);
+        register!(d, Aabb, Obb, compute_aabb_and_obb);
+        register!(d, Triangle, Triangle, compute_triangle_and_triangle);
+        register!(d, Obb, Plane, compute_obb_and_plane);
+        register!(d, Obb, Triangle, compute_obb_and_triangle);
+        register!(d, Obb, Obb, compute_obb_and_obb);
+        register!(d, Obb, Frustum, compute_obb_and_frustum);
+        register!(d, Plane, Frustum, compute_plane_and_frustum);
+        register!(d, Triangle, Frustum, compute_triangle_and_frustum);
+        register!(d, Frustum, Frustum, compute_frustum_and_frustum);
+        d.set_fallback(Some(Arc::new(try_test_collision)));
+        d
+    }
+}
+
+impl Dispatcher {
+    // No handlers and no fallback.
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            fallback: None,
+        }
+    }
+
+    // Replaces the handler of `kind1` against `kind2`, the other order is untouched.
+    pub fn register(
+        &mut self,
+        kind1: GeomeryKind,
+        kind2: GeomeryKind,
+        f: impl Fn(&Geomery, &Geomery) -> Option<bool> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.handlers.insert((kind1, kind2), Arc::new(f));
+        self
+    }
+
+    // Registers `f` for `kind1` against `kind2` and swaps the arguments for the other order.
+    pub fn register_symmetric(
+        &mut self,
+        kind1: GeomeryKind,
+        kind2: GeomeryKind,
+        f: impl Fn(&Geomery, &Geomery) -> Option<bool> + Send + Sync + 'static,
+    ) -> &mut Self {
+        let f: Handler = Arc::new(f);
+        if kind1 != kind2 {
+            let reversed = f.clone();
+            self.handlers
+                .insert((kind2, kind1), Arc::new(move |g1, g2| reversed(g2, g1)));
+        }
+        self.handlers.insert((kind1, kind2), f);
+        self
+    }
+
+    pub fn remove(&mut self, kind1: GeomeryKind, kind2: GeomeryKind) -> Option<Handler> {
+        self.handlers.remove(&(kind1, kind2))
+    }
+
+    pub fn get(&self, kind1: GeomeryKind, kind2: GeomeryKind) -> Option<&Handler> {
+        self.handlers.get(&(kind1, kind2))
+    }
+
+    pub fn set_fallback(&mut self, fallback: Option<Handler>) -> &mut Self {
+        self.fallback = fallback;
+        self
+    }
+
+    // Compound parts are dispatched one by one, so overrides apply to them too.
+    pub fn try_test(&self, geomery1: &Geomery, geomery2: &Geomery) -> Option<bool> {
+        match (geomery1, geomery2) {
+            (Geomery::Compound(parts), g) => {
+                return compute_compound(parts, |part| self.try_test(part, g))
+            }
+            (g, Geomery::Compound(parts)) => {
+                return compute_compound(parts, |part| self.try_test(g, part))
+            }
+            _ => {}
+        }
+
+        if let Some(f) = self.get(geomery1.kind(), geomery2.kind()) {
+            return f(geomery1, geomery2);
+        }
+
+        let fallback = self.fallback.as_ref()?;
+        fallback(geomery1, geomery2).or_else(|| fallback(geomery2, geomery1))
+    }
+
+    pub fn test(&self, geomery1: &Geomery, geomery2: &Geomery) -> bool {
+        match self.try_test(geomery1, geomery2) {
+            Some(r) => r,
+            None => panic!(
+                "not support geomery type test collision!!! g1: {:?}, g2: {:?}",
+                geomery1, geomery2
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::test_geomeries;
+
+#[cfg(test)]
+fn find(geomeries: &[Geomery], kind: GeomeryKind) -> &Geomery {
+    geomeries.iter().find(|g| g.kind() == kind).unwrap()
+}
+
+#[test]
+fn test_dispatcher_default() {
+    let dispatcher = Dispatcher::default();
+    let geomeries = test_geomeries();
+
+    for g1 in &geomeries {
+        for g2 in &geomeries {
+            let expected = try_test_collision(g1, g2).or_else(|| try_test_collision(g2, g1));
+            assert_eq!(dispatcher.try_test(g1, g2), expected);
+            assert_eq!(dispatcher.try_test(g1, g2), dispatcher.try_test(g2, g1));
+        }
+    }
+
+    let point = find(&geomeries, GeomeryKind::Point);
+    let sphere = find(&geomeries, GeomeryKind::Sphere);
+    let aabb = find(&geomeries, GeomeryKind::Aabb);
+    let frustum = find(&geomeries, GeomeryKind::Frustum);
+
+    assert_eq!(dispatcher.test(sphere, point), true);
+    assert!(dispatcher
+        .get(GeomeryKind::Frustum, GeomeryKind::Sphere)
+        .is_some());
+    assert!(dispatcher
+        .get(GeomeryKind::Point, GeomeryKind::Frustum)
+        .is_none());
+    assert_eq!(dispatcher.try_test(point, frustum), None);
+    assert_eq!(Dispatcher::new().try_test(sphere, aabb), None);
+}
+
+#[test]
+fn test_dispatcher_override() {
+    let mut dispatcher = Dispatcher::default();
+    let geomeries = test_geomeries();
+    let point = find(&geomeries, GeomeryKind::Point);
+    let sphere = find(&geomeries, GeomeryKind::Sphere);
+    let frustum = find(&geomeries, GeomeryKind::Frustum);
+
+    dispatcher.register(GeomeryKind::Point, GeomeryKind::Frustum, |_, _| Some(true));
+    assert_eq!(dispatcher.try_test(point, frustum), Some(true));
+    assert_eq!(dispatcher.try_test(frustum, point), None);
+
+    let compound = Geomery::Compound(vec![point.clone(), sphere.clone()]);
+    assert_eq!(dispatcher.test(&compound, frustum), true);
+
+    assert!(dispatcher
+        .remove(GeomeryKind::Point, GeomeryKind::Frustum)
+        .is_some());
+    assert_eq!(dispatcher.try_test(&compound, frustum), None);
+
+    assert_eq!(dispatcher.test(sphere, frustum), false);
+    dispatcher.register_symmetric(GeomeryKind::Sphere, GeomeryKind::Frustum, |_, _| Some(true));
+    assert_eq!(dispatcher.test(sphere, frustum), true);
+    assert_eq!(dispatcher.test(frustum, sphere), true);
+}
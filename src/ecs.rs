@@ -0,0 +1,488 @@
+use bevy_ecs::entity::EntityHashMap;
+use bevy_ecs::prelude::*;
+
+use crate::shape::{Aabb, Ball, Cuboid, Isometry, Obb, Point3, Ray, Real, Sphere, Vector3};
+use crate::{as_shape, distance, raycast, try_test_collision, Geomery};
+
+use parry3d::bounding_volume::{Aabb as AABB, BoundingVolume};
+use parry3d::partitioning::{Qbvh, QbvhUpdateWorkspace};
+use parry3d::query::visitors::RayIntersectionsVisitor;
+use parry3d::query::RayCast;
+
+// Collider shape in local space, placed in the world by `WorldTransform`.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum Collider {
+    Point,
+    Sphere(Real),
+    // Half extents, the rotation of the transform is ignored.
+    Aabb(Vector3),
+    // Half extents.
+    Obb(Vector3),
+}
+
+impl Collider {
+    pub fn to_geomery(&self, transform: &Isometry) -> Geomery {
+        let center = Point3::from(transform.translation.vector);
+        match self {
+            Collider::Point => Geomery::Point(center),
+            Collider::Sphere(radius) => Geomery::Sphere(Sphere(
+                Ball::new(*radius),
+                Isometry::from(transform.translation),
+            )),
+            Collider::Aabb(half_extents) => {
+                Geomery::Aabb(Aabb::new(center - half_extents, center + half_extents))
+            }
+            Collider::Obb(half_extents) => {
+                Geomery::Obb(Obb(Cuboid::new(*half_extents), *transform))
+            }
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct WorldTransform(pub Isometry);
+
+impl Default for WorldTransform {
+    fn default() -> Self {
+        Self(Isometry::identity())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub entity: Entity,
+    pub toi: Real,
+    pub point: Point3,
+}
+
+// World space colliders in a bounding volume tree, kept in sync incrementally by
+// `sync_broadphase`. Changes are seen by queries after the next `update`.
+#[derive(Resource, Default)]
+pub struct Broadphase {
+    slots: Vec<Option<Entry>>,
+    free: Vec<u32>,
+    map: EntityHashMap<u32>,
+    tree: Qbvh<u32>,
+    workspace: QbvhUpdateWorkspace,
+}
+
+struct Entry {
+    entity: Entity,
+    geomery: Geomery,
+    bounds: AABB,
+}
+
+fn bounds(geomery: &Geomery) -> Option<AABB> {
+    as_shape(geomery).map(|(m, s)| s.compute_aabb(&m))
+}
+
+// Single order pairs fall back to the reversed order, then to the distance query.
+fn intersects(g1: &Geomery, g2: &Geomery) -> bool {
+    try_test_collision(g1, g2)
+        .or_else(|| try_test_collision(g2, g1))
+        .unwrap_or_else(|| distance(g1, g2).is_ok_and(|d| d <= 0.0))
+}
+
+impl Broadphase {
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.free.clear();
+        self.map.clear();
+        self.tree = Qbvh::new();
+    }
+
+    // Inserts or replaces the collider of `entity`, geomeries without bounds are removed.
+    pub fn insert(&mut self, entity: Entity, geomery: Geomery) {
+        let Some(bounds) = bounds(&geomery) else {
+            self.remove(entity);
+            return;
+        };
+
+        let slot = match self.map.get(&entity) {
+            Some(slot) => *slot,
+            None => {
+                let slot = self.free.pop().unwrap_or_else(|| {
+                    self.slots.push(None);
+                    self.slots.len() as u32 - 1
+                });
+                self.map.insert(entity, slot);
+                slot
+            }
+        };
+        self.slots[slot as usize] = Some(Entry {
+            entity,
+            geomery,
+            bounds,
+        });
+        self.tree.pre_update_or_insert(slot);
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<Geomery> {
+        let slot = self.map.remove(&entity)?;
+        self.tree.remove(slot);
+        self.free.push(slot);
+        self.slots[slot as usize].take().map(|e| e.geomery)
+    }
+
+    // Refits the tree around the inserted, moved and removed colliders.
+    pub fn update(&mut self) {
+        let slots = &self.slots;
+        self.tree.refit(0.0, &mut self.workspace, |slot| {
+            slots[*slot as usize]
+                .as_ref()
+                .map_or(AABB::new_invalid(), |e| e.bounds)
+        });
+        self.tree.rebalance(0.0, &mut self.workspace);
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&Geomery> {
+        self.entry(*self.map.get(&entity)?).map(|e| &e.geomery)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &Geomery)> {
+        self.entries().map(|e| (e.entity, &e.geomery))
+    }
+
+    fn entry(&self, slot: u32) -> Option<&Entry> {
+        self.slots.get(slot as usize)?.as_ref()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.slots.iter().flatten()
+    }
+
+    // Closest hit along the ray.
+    pub fn raycast(&self, ray: &Ray, max_toi: Real) -> Option<RayHit> {
+        let mut candidates = Vec::new();
+        let mut collect = |slot: &u32| {
+            candidates.push(*slot);
+            true
+        };
+        self.tree
+            .traverse_depth_first(&mut RayIntersectionsVisitor::new(
+                ray,
+                max_toi,
+                &mut collect,
+            ));
+
+        let mut hit: Option<RayHit> = None;
+        for entry in candidates.into_iter().filter_map(|slot| self.entry(slot)) {
+            let max_toi = hit.map_or(max_toi, |h| h.toi);
+            if !entry.bounds.intersects_local_ray(ray, max_toi) {
+                continue;
+            }
+
+            if let Some(toi) = raycast(ray, &entry.geomery, max_toi) {
+                if hit.is_none_or(|h| toi < h.toi) {
+                    hit = Some(RayHit {
+                        entity: entry.entity,
+                        toi,
+                        point: ray.point_at(toi),
+                    });
+                }
+            }
+        }
+        hit
+    }
+
+    // Geomeries without bounds are tested against every collider.
+    pub fn overlap(&self, geomery: &Geomery) -> Vec<Entity> {
+        let mut slots = match bounds(geomery) {
+            Some(b) => {
+                let mut slots = Vec::new();
+                self.tree.intersect_aabb(&b, &mut slots);
+                slots
+            }
+            None => (0..self.slots.len() as u32).collect(),
+        };
+        slots.sort_unstable();
+        slots
+            .into_iter()
+            .filter_map(|slot| self.entry(slot))
+            .filter(|e| intersects(geomery, &e.geomery))
+            .map(|e| e.entity)
+            .collect()
+    }
+
+    // Overlapping collider pairs, sweep and prune along x.
+    pub fn pairs(&self) -> Vec<(Entity, Entity)> {
+        let mut order: Vec<&Entry> = self.entries().collect();
+        order.sort_by(|a, b| a.bounds.mins.x.total_cmp(&b.bounds.mins.x));
+
+        let mut pairs = Vec::new();
+        for (n, a) in order.iter().enumerate() {
+            for b in &order[n + 1..] {
+                if b.bounds.mins.x > a.bounds.maxs.x {
+                    break;
+                }
+                if a.bounds.intersects(&b.bounds) && intersects(&a.geomery, &b.geomery) {
+                    pairs.push((a.entity, b.entity));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+// Rays queued during the frame, `results` is indexed by the value returned from `push`.
+#[derive(Resource, Default)]
+pub struct RayQueries {
+    queued: Vec<(Ray, Real)>,
+    results: Vec<Option<RayHit>>,
+}
+
+impl RayQueries {
+    pub fn push(&mut self, ray: Ray, max_toi: Real) -> usize {
+        self.queued.push((ray, max_toi));
+        self.queued.len() - 1
+    }
+
+    pub fn results(&self) -> &[Option<RayHit>] {
+        &self.results
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct OverlapQueries {
+    queued: Vec<Geomery>,
+    results: Vec<Vec<Entity>>,
+}
+
+impl OverlapQueries {
+    pub fn push(&mut self, geomery: Geomery) -> usize {
+        self.queued.push(geomery);
+        self.queued.len() - 1
+    }
+
+    pub fn results(&self) -> &[Vec<Entity>] {
+        &self.results
+    }
+}
+
+type ChangedColliders = Or<(Changed<Collider>, Changed<WorldTransform>)>;
+
+// Only colliders added, moved or removed since the last run are touched.
+pub fn sync_broadphase(
+    changed: Query<(Entity, &Collider, &WorldTransform), ChangedColliders>,
+    mut removed_colliders: RemovedComponents<Collider>,
+    mut removed_transforms: RemovedComponents<WorldTransform>,
+    mut broadphase: ResMut<Broadphase>,
+) {
+    for entity in removed_colliders.read().chain(removed_transforms.read()) {
+        broadphase.remove(entity);
+    }
+    for (entity, collider, transform) in changed.iter() {
+        broadphase.insert(entity, collider.to_geomery(&transform.0));
+    }
+    broadphase.update();
+}
+
+pub fn run_ray_queries(broadphase: Res<Broadphase>, mut queries: ResMut<RayQueries>) {
+    let queries = &mut *queries;
+    queries.results = queries
+        .queued
+        .drain(..)
+        .map(|(ray, max_toi)| broadphase.raycast(&ray, max_toi))
+        .collect();
+}
+
+pub fn run_overlap_queries(broadphase: Res<Broadphase>, mut queries: ResMut<OverlapQueries>) {
+    let queries = &mut *queries;
+    queries.results = queries
+        .queued
+        .drain(..)
+        .map(|geomery| broadphase.overlap(&geomery))
+        .collect();
+}
+
+// Inserts the resources and adds the systems in order: sync, then queries.
+pub fn init(world: &mut World, schedule: &mut Schedule) {
+    world.init_resource::<Broadphase>();
+    world.init_resource::<RayQueries>();
+    world.init_resource::<OverlapQueries>();
+
+    schedule.add_systems((sync_broadphase, run_ray_queries, run_overlap_queries).chain());
+}
+
+#[cfg(test)]
+fn test_world() -> (World, Schedule, [Entity; 3]) {
+    let mut world = World::new();
+    let mut schedule = Schedule::default();
+    init(&mut world, &mut schedule);
+
+    let sphere = world
+        .spawn((
+            Collider::Sphere(1.0),
+            WorldTransform(Isometry::translation(0.0, 0.0, 0.0)),
+        ))
+        .id();
+    let aabb = world
+        .spawn((
+            Collider::Aabb(Vector3::new(1.0, 1.0, 1.0)),
+            WorldTransform(Isometry::translation(1.5, 0.0, 0.0)),
+        ))
+        .id();
+    let obb = world
+        .spawn((
+            Collider::Obb(Vector3::new(1.0, 1.0, 1.0)),
+            WorldTransform(Isometry::new(
+                Vector3::new(10.0, 0.0, 0.0),
+                Vector3::new(0.0, 0.0, 0.5),
+            )),
+        ))
+        .id();
+
+    (world, schedule, [sphere, aabb, obb])
+}
+
+#[test]
+fn test_ecs_sync() {
+    let (mut world, mut schedule, [sphere, aabb, obb]) = test_world();
+    schedule.run(&mut world);
+
+    let broadphase = world.resource::<Broadphase>();
+    assert_eq!(broadphase.len(), 3);
+    assert_eq!(broadphase.pairs(), vec![(sphere, aabb)]);
+
+    world.get_mut::<WorldTransform>(obb).unwrap().0 = Isometry::translation(3.0, 0.0, 0.0);
+    world.despawn(sphere);
+    schedule.run(&mut world);
+
+    let broadphase = world.resource::<Broadphase>();
+    assert_eq!(broadphase.len(), 2);
+    assert_eq!(broadphase.pairs(), vec![(aabb, obb)]);
+    assert!(broadphase.get(sphere).is_none());
+}
+
+#[test]
+fn test_ecs_incremental_sync() {
+    let mut world = World::new();
+    let mut schedule = Schedule::default();
+    init(&mut world, &mut schedule);
+
+    let center = |i: usize| Point3::new((i % 10) as Real * 3.0, (i / 10) as Real * 3.0, 0.0);
+    let entities: Vec<Entity> = (0..100)
+        .map(|i| {
+            let p = center(i);
+            world
+                .spawn((
+                    Collider::Sphere(1.0),
+                    WorldTransform(Isometry::translation(p.x, p.y, p.z)),
+                ))
+                .id()
+        })
+        .collect();
+    schedule.run(&mut world);
+
+    let broadphase = world.resource::<Broadphase>();
+    assert_eq!(broadphase.len(), 100);
+    for (i, entity) in entities.iter().enumerate() {
+        assert_eq!(
+            broadphase.overlap(&Geomery::Point(center(i))),
+            vec![*entity]
+        );
+    }
+
+    // Moves every even collider away, despawns a few and strips one collider.
+    for i in (0..100).step_by(2) {
+        world.get_mut::<WorldTransform>(entities[i]).unwrap().0 =
+            Isometry::translation(center(i).x, center(i).y, 50.0);
+    }
+    world.despawn(entities[1]);
+    world.despawn(entities[2]);
+    world.entity_mut(entities[3]).remove::<Collider>();
+    schedule.run(&mut world);
+
+    let broadphase = world.resource::<Broadphase>();
+    assert_eq!(broadphase.len(), 97);
+    assert!(broadphase.get(entities[3]).is_none());
+    for (i, entity) in entities.iter().enumerate().skip(4) {
+        let expected = if i % 2 == 0 { vec![] } else { vec![*entity] };
+        assert_eq!(broadphase.overlap(&Geomery::Point(center(i))), expected);
+        if i % 2 == 0 {
+            let moved = Point3::new(center(i).x, center(i).y, 50.0);
+            assert_eq!(broadphase.overlap(&Geomery::Point(moved)), vec![*entity]);
+        }
+    }
+
+    let ray = Ray::new(Point3::new(-5.0, 3.0, 0.0), Vector3::x());
+    assert_eq!(
+        broadphase.raycast(&ray, Real::MAX).unwrap().entity,
+        entities[11]
+    );
+
+    // Freed slots are reused.
+    let entity = world
+        .spawn((
+            Collider::Point,
+            WorldTransform(Isometry::translation(0.0, 0.0, -9.0)),
+        ))
+        .id();
+    schedule.run(&mut world);
+    let broadphase = world.resource::<Broadphase>();
+    assert_eq!(broadphase.len(), 98);
+    assert_eq!(
+        broadphase.overlap(&Geomery::Sphere(Sphere(
+            Ball::new(0.5),
+            Isometry::translation(0.0, 0.0, -9.0)
+        ))),
+        vec![entity]
+    );
+}
+
+#[test]
+fn test_ecs_ray_queries() {
+    let (mut world, mut schedule, [sphere, _, obb]) = test_world();
+
+    let mut queries = world.resource_mut::<RayQueries>();
+    let left = queries.push(
+        Ray::new(Point3::new(-5.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0)),
+        Real::MAX,
+    );
+    let right = queries.push(
+        Ray::new(Point3::new(20.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0)),
+        Real::MAX,
+    );
+    let miss = queries.push(
+        Ray::new(Point3::new(0.0, 5.0, 0.0), Vector3::new(1.0, 0.0, 0.0)),
+        Real::MAX,
+    );
+    schedule.run(&mut world);
+
+    let results = world.resource::<RayQueries>().results();
+    let hit = results[left].unwrap();
+    assert_eq!(hit.entity, sphere);
+    assert_eq!(hit.toi, 4.0);
+    assert_eq!(results[right].unwrap().entity, obb);
+    assert_eq!(results[miss], None);
+
+    schedule.run(&mut world);
+    assert!(world.resource::<RayQueries>().results().is_empty());
+}
+
+#[test]
+fn test_ecs_overlap_queries() {
+    let (mut world, mut schedule, [sphere, aabb, obb]) = test_world();
+
+    let mut queries = world.resource_mut::<OverlapQueries>();
+    let center = queries.push(Geomery::Point(Point3::new(0.8, 0.0, 0.0)));
+    let far = queries.push(Geomery::Sphere(Sphere(
+        Ball::new(0.5),
+        Isometry::translation(10.0, 0.0, 0.0),
+    )));
+    let none = queries.push(Geomery::Point(Point3::new(0.0, 5.0, 0.0)));
+    schedule.run(&mut world);
+
+    let results = world.resource::<OverlapQueries>().results();
+    assert_eq!(results[center], vec![sphere, aabb]);
+    assert_eq!(results[far], vec![obb]);
+    assert!(results[none].is_empty());
+}
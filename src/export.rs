@@ -0,0 +1,447 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::debug::{debug_lines, debug_triangles, Face, Line};
+use crate::shape::Point3;
+use crate::Geomery;
+
+#[allow(unused_imports)]
+use crate::shape::{Aabb, Ball, Isometry, Ray, Sphere, Vector3};
+
+struct Object {
+    name: String,
+    faces: Vec<Face>,
+    lines: Vec<Line>,
+}
+
+// Collects named shapes and writes them to OBJ, glTF or binary glTF.
+// Solid shapes are tessellated, points and rays become line primitives.
+#[derive(Default)]
+pub struct Exporter {
+    objects: Vec<Object>,
+}
+
+impl Exporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: impl Into<String>, geomery: &Geomery) -> &mut Self {
+        let faces = debug_triangles(geomery);
+        let lines = if faces.is_empty() {
+            debug_lines(geomery)
+        } else {
+            Vec::new()
+        };
+
+        self.objects.push(Object {
+            name: name.into(),
+            faces,
+            lines,
+        });
+        self
+    }
+
+    pub fn add_lines(&mut self, name: impl Into<String>, lines: &[Line]) -> &mut Self {
+        self.objects.push(Object {
+            name: name.into(),
+            faces: Vec::new(),
+            lines: lines.to_vec(),
+        });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    // The format follows the extension, `.obj`, `.gltf` or `.glb`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        let write: fn(&Self, &mut BufWriter<File>) -> io::Result<()> = match extension.as_deref() {
+            Some("obj") => |e, w| e.write_obj(w),
+            Some("gltf") => |e, w| e.write_gltf(w),
+            Some("glb") => |e, w| e.write_glb(w),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unsupported export format: {}", path.display()),
+                ))
+            }
+        };
+
+        let mut w = BufWriter::new(File::create(path)?);
+        write(self, &mut w)?;
+        w.flush()
+    }
+
+    pub fn write_obj<W: Write>(&self, mut w: W) -> io::Result<()> {
+        // Obj indices are 1 based and global to the file.
+        let mut base = 1;
+        for object in &self.objects {
+            writeln!(w, "o {}", obj_name(&object.name))?;
+
+            for p in object.faces.iter().flatten() {
+                writeln!(w, "v {} {} {}", p.x, p.y, p.z)?;
+            }
+            for (a, b) in &object.lines {
+                writeln!(w, "v {} {} {}", a.x, a.y, a.z)?;
+                writeln!(w, "v {} {} {}", b.x, b.y, b.z)?;
+            }
+
+            for _ in &object.faces {
+                writeln!(w, "f {} {} {}", base, base + 1, base + 2)?;
+                base += 3;
+            }
+            for _ in &object.lines {
+                writeln!(w, "l {} {}", base, base + 1)?;
+                base += 2;
+            }
+        }
+        Ok(())
+    }
+
+    // glTF json with the buffer embedded as a base64 data uri.
+    pub fn write_gltf<W: Write>(&self, mut w: W) -> io::Result<()> {
+        let json = self.gltf_json(&mut Vec::new(), true);
+        w.write_all(json.as_bytes())
+    }
+
+    pub fn write_glb<W: Write>(&self, mut w: W) -> io::Result<()> {
+        let mut bin = Vec::new();
+        let json = self.gltf_json(&mut bin, false);
+
+        // Chunks are 4 byte aligned, json with spaces and binary with zeros.
+        let mut json = json.into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        bin.resize(bin.len().next_multiple_of(4), 0);
+
+        let mut length = 12 + 8 + json.len();
+        if !bin.is_empty() {
+            length += 8 + bin.len();
+        }
+
+        w.write_all(b"glTF")?;
+        w.write_all(&2u32.to_le_bytes())?;
+        w.write_all(&(length as u32).to_le_bytes())?;
+
+        w.write_all(&(json.len() as u32).to_le_bytes())?;
+        w.write_all(b"JSON")?;
+        w.write_all(&json)?;
+
+        if !bin.is_empty() {
+            w.write_all(&(bin.len() as u32).to_le_bytes())?;
+            w.write_all(b"BIN\0")?;
+            w.write_all(&bin)?;
+        }
+        Ok(())
+    }
+
+    // Fills `bin` with the positions, `embed` puts it in the json as a data uri.
+    fn gltf_json(&self, bin: &mut Vec<u8>, embed: bool) -> String {
+        let mut views = Vec::new();
+        let mut accessors = Vec::new();
+        let mut meshes = Vec::new();
+        let mut nodes = Vec::new();
+
+        for object in &self.objects {
+            let mut primitives = Vec::new();
+
+            let face_points: Vec<Point3> = object.faces.iter().flatten().copied().collect();
+            let line_points: Vec<Point3> =
+                object.lines.iter().flat_map(|(a, b)| [*a, *b]).collect();
+
+            // glTF primitive modes, 4 is triangles and 1 is lines.
+            for (points, mode) in [(face_points, 4), (line_points, 1)] {
+                if points.is_empty() {
+                    continue;
+                }
+
+                let offset = bin.len();
+                let (mut min, mut max) = ([f32::MAX; 3], [f32::MIN; 3]);
+                for p in &points {
+                    // glTF positions are always f32.
+                    #[allow(clippy::unnecessary_cast)]
+                    let p = [p.x as f32, p.y as f32, p.z as f32];
+                    for i in 0..3 {
+                        min[i] = min[i].min(p[i]);
+                        max[i] = max[i].max(p[i]);
+                        bin.extend_from_slice(&p[i].to_le_bytes());
+                    }
+                }
+
+                views.push(format!(
+                    r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":34962}}"#,
+                    offset,
+                    bin.len() - offset
+                ));
+                accessors.push(format!(
+                    r#"{{"bufferView":{},"componentType":5126,"count":{},"type":"VEC3","min":[{},{},{}],"max":[{},{},{}]}}"#,
+                    views.len() - 1,
+                    points.len(),
+                    min[0],
+                    min[1],
+                    min[2],
+                    max[0],
+                    max[1],
+                    max[2]
+                ));
+                primitives.push(format!(
+                    r#"{{"attributes":{{"POSITION":{}}},"mode":{}}}"#,
+                    accessors.len() - 1,
+                    mode
+                ));
+            }
+
+            let name = json_string(&object.name);
+            if primitives.is_empty() {
+                nodes.push(format!(r#"{{"name":{}}}"#, name));
+            } else {
+                meshes.push(format!(
+                    r#"{{"name":{},"primitives":[{}]}}"#,
+                    name,
+                    primitives.join(",")
+                ));
+                nodes.push(format!(
+                    r#"{{"name":{},"mesh":{}}}"#,
+                    name,
+                    meshes.len() - 1
+                ));
+            }
+        }
+
+        let mut json = format!(
+            r#"{{"asset":{{"version":"2.0","generator":"pi_collision"}},"scene":0,"scenes":[{{"nodes":[{}]}}],"nodes":[{}]"#,
+            (0..nodes.len())
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            nodes.join(",")
+        );
+        if !bin.is_empty() {
+            let uri = if embed {
+                format!(
+                    r#","uri":"data:application/octet-stream;base64,{}""#,
+                    base64(bin)
+                )
+            } else {
+                String::new()
+            };
+            json.push_str(&format!(
+                r#","meshes":[{}],"accessors":[{}],"bufferViews":[{}],"buffers":[{{"byteLength":{}{}}}]"#,
+                meshes.join(","),
+                accessors.join(","),
+                views.join(","),
+                bin.len(),
+                uri
+            ));
+        }
+        json.push('}');
+        json
+    }
+}
+
+// Obj names end at whitespace.
+fn obj_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect();
+    if name.is_empty() {
+        "unnamed".to_string()
+    } else {
+        name
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Temporary directory removed when dropped.
+#[cfg(test)]
+pub(crate) struct TestDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl TestDir {
+    pub(crate) fn new(name: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("pi_collision_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    pub(crate) fn join(&self, file: &str) -> std::path::PathBuf {
+        self.0.join(file)
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+fn test_exporter() -> Exporter {
+    let mut exporter = Exporter::new();
+    exporter
+        .add(
+            "box",
+            &Geomery::Aabb(Aabb::new(
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 2.0, 3.0),
+            )),
+        )
+        .add(
+            "hit \"ray\"",
+            &Geomery::Ray(Ray::new(Point3::origin(), Vector3::x())),
+        )
+        .add_lines("segment", &[(Point3::origin(), Point3::new(0.0, 1.0, 0.0))]);
+    exporter
+}
+
+#[test]
+fn test_export_obj() {
+    let mut out = Vec::new();
+    test_exporter().write_obj(&mut out).unwrap();
+    let obj = String::from_utf8(out).unwrap();
+
+    let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(count("o "), 3);
+    assert_eq!(count("f "), 12);
+    assert_eq!(count("l "), 2);
+    assert_eq!(count("v "), 12 * 3 + 2 * 2);
+
+    assert!(obj.contains("o hit_\"ray\"\n"));
+    assert!(obj.ends_with("l 39 40\n"));
+}
+
+#[test]
+fn test_export_glb() {
+    let mut out = Vec::new();
+    test_exporter().write_glb(&mut out).unwrap();
+
+    let u32_at = |i: usize| u32::from_le_bytes(out[i..i + 4].try_into().unwrap()) as usize;
+    assert_eq!(&out[0..4], b"glTF");
+    assert_eq!(u32_at(4), 2);
+    assert_eq!(u32_at(8), out.len());
+    assert_eq!(out.len() % 4, 0);
+
+    let json_len = u32_at(12);
+    assert_eq!(&out[16..20], b"JSON");
+    let json: serde_json::Value = serde_json::from_slice(&out[20..20 + json_len]).unwrap();
+
+    let bin_len = u32_at(20 + json_len);
+    assert_eq!(&out[24 + json_len..28 + json_len], b"BIN\0");
+    assert_eq!(json["buffers"][0]["byteLength"], (12 * 3 + 2 * 2) * 12);
+    assert_eq!(bin_len, (12 * 3 + 2 * 2) * 12);
+
+    assert_eq!(json["nodes"][1]["name"], "hit \"ray\"");
+    assert_eq!(json["meshes"][0]["primitives"][0]["mode"], 4);
+    assert_eq!(json["meshes"][1]["primitives"][0]["mode"], 1);
+    assert_eq!(json["accessors"][0]["count"], 36);
+    assert_eq!(json["accessors"][0]["max"][2], 3.0);
+}
+
+#[test]
+fn test_export_gltf() {
+    let mut out = Vec::new();
+    test_exporter().write_gltf(&mut out).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+    let uri = json["buffers"][0]["uri"].as_str().unwrap();
+    let data = uri
+        .strip_prefix("data:application/octet-stream;base64,")
+        .unwrap();
+    assert_eq!(data.len(), ((12 * 3 + 2 * 2) * 12_usize).div_ceil(3) * 4);
+    assert_eq!(json["buffers"][0]["byteLength"], (12 * 3 + 2 * 2) * 12);
+    assert_eq!(json["nodes"][1]["name"], "hit \"ray\"");
+    assert_eq!(json["meshes"][1]["primitives"][0]["mode"], 1);
+    #[cfg(feature = "import")]
+    assert!(gltf::import_slice(&out).is_ok());
+
+    assert_eq!(base64(b"glTF"), "Z2xURg==");
+    assert_eq!(base64(b"glTF!"), "Z2xURiE=");
+    assert_eq!(base64(b"glTF!!"), "Z2xURiEh");
+}
+
+#[test]
+fn test_export_save() {
+    let dir = TestDir::new("export");
+    let exporter = test_exporter();
+
+    exporter.save(dir.join("scene.obj")).unwrap();
+    assert!(std::fs::read_to_string(dir.join("scene.obj"))
+        .unwrap()
+        .starts_with("o box\n"));
+
+    exporter.save(dir.join("scene.GLB")).unwrap();
+    assert_eq!(
+        &std::fs::read(dir.join("scene.GLB")).unwrap()[0..4],
+        b"glTF"
+    );
+
+    exporter.save(dir.join("scene.gltf")).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(dir.join("scene.gltf")).unwrap()).unwrap();
+    assert_eq!(json["asset"]["version"], "2.0");
+
+    for name in ["scene.ojb", "scene"] {
+        let err = exporter.save(dir.join(name)).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!dir.join(name).exists());
+    }
+}
+
+#[test]
+fn test_export_empty() {
+    let mut out = Vec::new();
+    Exporter::new().write_glb(&mut out).unwrap();
+    assert_eq!(out.len() % 4, 0);
+    assert_eq!(&out[16..20], b"JSON");
+    assert_eq!(
+        out.len(),
+        20 + u32::from_le_bytes(out[12..16].try_into().unwrap()) as usize
+    );
+}
@@ -0,0 +1,504 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use crate::shape::{
+    Aabb, Ball, ConvexHull, Cuboid, Isometry, Matrix4, Obb, Point3, Real, Sphere, TriMesh, Vector3,
+};
+use crate::Geomery;
+
+use parry3d::na::{Matrix3, Rotation3, Translation3, UnitQuaternion};
+
+#[allow(unused_imports)]
+use crate::export::Exporter;
+#[allow(unused_imports)]
+use crate::GeomeryKind;
+
+// Unreal style collision proxy prefixes.
+const CONVEX_PREFIX: &str = "UCX_";
+const BOX_PREFIX: &str = "UBX_";
+const SPHERE_PREFIX: &str = "USP_";
+
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),
+    Obj(tobj::LoadError),
+    Gltf(gltf::Error),
+    // The file extension is neither obj, gltf nor glb.
+    Unsupported(String),
+    // Mesh name, its triangles reference missing vertices or it has too few points.
+    InvalidMesh(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(e) => write!(f, "io error: {}", e),
+            ImportError::Obj(e) => write!(f, "obj error: {}", e),
+            ImportError::Gltf(e) => write!(f, "gltf error: {}", e),
+            ImportError::Unsupported(ext) => write!(f, "unsupported file extension: {:?}", ext),
+            ImportError::InvalidMesh(name) => write!(f, "invalid collision mesh: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImportError::Io(e) => Some(e),
+            ImportError::Obj(e) => Some(e),
+            ImportError::Gltf(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> Self {
+        ImportError::Io(e)
+    }
+}
+
+impl From<tobj::LoadError> for ImportError {
+    fn from(e: tobj::LoadError) -> Self {
+        ImportError::Obj(e)
+    }
+}
+
+impl From<gltf::Error> for ImportError {
+    fn from(e: gltf::Error) -> Self {
+        ImportError::Gltf(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedShape {
+    // Mesh name with the proxy prefix and numbering removed.
+    pub name: String,
+    pub geomery: Geomery,
+}
+
+// Triangles in world space.
+struct Mesh {
+    name: String,
+    vertices: Vec<Point3>,
+    indices: Vec<[u32; 3]>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Proxy {
+    Convex,
+    Box,
+    Sphere,
+}
+
+// Loads by extension, `.obj`, `.gltf` or `.glb`.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<ImportedShape>, ImportError> {
+    let path = path.as_ref();
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "obj" => load_obj(path),
+        "gltf" | "glb" => load_gltf(path),
+        _ => Err(ImportError::Unsupported(ext)),
+    }
+}
+
+pub fn load_obj(path: impl AsRef<Path>) -> Result<Vec<ImportedShape>, ImportError> {
+    let (models, _) = tobj::load_obj(path.as_ref(), &tobj::GPU_LOAD_OPTIONS)?;
+
+    let meshes = models
+        .into_iter()
+        .map(|model| Mesh {
+            vertices: model
+                .mesh
+                .positions
+                .chunks_exact(3)
+                .map(|p| Point3::new(p[0] as Real, p[1] as Real, p[2] as Real))
+                .collect(),
+            indices: model
+                .mesh
+                .indices
+                .chunks_exact(3)
+                .map(|t| [t[0], t[1], t[2]])
+                .collect(),
+            name: model.name,
+        })
+        .collect();
+    classify(meshes)
+}
+
+pub fn load_gltf(path: impl AsRef<Path>) -> Result<Vec<ImportedShape>, ImportError> {
+    let path = path.as_ref();
+    let gltf = gltf::Gltf::open(path)?;
+    let buffers = gltf::import_buffers(&gltf.document, path.parent(), gltf.blob.clone())?;
+
+    let mut meshes = Vec::new();
+    let scene = gltf
+        .document
+        .default_scene()
+        .or_else(|| gltf.document.scenes().next());
+    if let Some(scene) = scene {
+        for node in scene.nodes() {
+            gltf_node(&node, &Matrix4::identity(), &buffers, &mut meshes);
+        }
+    }
+    classify(meshes)
+}
+
+fn gltf_node(
+    node: &gltf::Node,
+    parent: &Matrix4,
+    buffers: &[gltf::buffer::Data],
+    meshes: &mut Vec<Mesh>,
+) {
+    let local = Matrix4::from(node.transform().matrix().map(|c| c.map(|v| v as Real)));
+    let world = parent * local;
+
+    if let Some(mesh) = node.mesh() {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for primitive in mesh.primitives() {
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                continue;
+            }
+
+            let reader = primitive.reader(|b| buffers.get(b.index()).map(|d| &d.0[..]));
+            let Some(positions) = reader.read_positions() else {
+                continue;
+            };
+
+            let base = vertices.len() as u32;
+            vertices.extend(positions.map(|p| {
+                world.transform_point(&Point3::new(p[0] as Real, p[1] as Real, p[2] as Real))
+            }));
+            let primitive_indices: Vec<u32> = match reader.read_indices() {
+                Some(i) => i.into_u32().collect(),
+                None => (0..vertices.len() as u32 - base).collect(),
+            };
+            indices.extend(
+                primitive_indices
+                    .chunks_exact(3)
+                    .map(|t| [t[0] + base, t[1] + base, t[2] + base]),
+            );
+        }
+
+        let name = node.name().or(mesh.name()).unwrap_or_default();
+        meshes.push(Mesh {
+            name: name.to_string(),
+            vertices,
+            indices,
+        });
+    }
+
+    for child in node.children() {
+        gltf_node(&child, &world, buffers, meshes);
+    }
+}
+
+fn proxy(name: &str) -> Option<(Proxy, &str)> {
+    [
+        (CONVEX_PREFIX, Proxy::Convex),
+        (BOX_PREFIX, Proxy::Box),
+        (SPHERE_PREFIX, Proxy::Sphere),
+    ]
+    .iter()
+    .find_map(|(prefix, kind)| name.strip_prefix(prefix).map(|base| (*kind, base)))
+}
+
+// `Rock_01` and `Rock.001` both belong to `Rock`.
+fn base_name(name: &str) -> &str {
+    let mut name = name;
+    for sep in ['.', '_'] {
+        if let Some((base, number)) = name.rsplit_once(sep) {
+            if !base.is_empty() && !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit())
+            {
+                name = base;
+            }
+        }
+    }
+    name
+}
+
+// Proxies sharing a base name become one shape, a compound when there are several.
+// Meshes with proxies are render meshes and are skipped, the others are kept as triangle meshes.
+// Meshes without triangles, such as line objects, are not collision shapes and are skipped too.
+fn classify(meshes: Vec<Mesh>) -> Result<Vec<ImportedShape>, ImportError> {
+    let mut proxies: Vec<ImportedShape> = Vec::new();
+    let mut others = Vec::new();
+
+    for mesh in meshes {
+        let Some((kind, base)) = proxy(&mesh.name) else {
+            others.push(mesh);
+            continue;
+        };
+
+        let geomery =
+            fit(kind, &mesh).ok_or_else(|| ImportError::InvalidMesh(mesh.name.clone()))?;
+        let base = base_name(base);
+        match proxies.iter_mut().find(|s| s.name == base) {
+            Some(ImportedShape {
+                geomery: Geomery::Compound(parts),
+                ..
+            }) => parts.push(geomery),
+            Some(shape) => {
+                let first = shape.geomery.clone();
+                shape.geomery = Geomery::Compound(vec![first, geomery]);
+            }
+            None => proxies.push(ImportedShape {
+                name: base.to_string(),
+                geomery,
+            }),
+        }
+    }
+
+    let mut shapes = Vec::new();
+    for mesh in others {
+        if mesh.indices.is_empty() || proxies.iter().any(|s| s.name == base_name(&mesh.name)) {
+            continue;
+        }
+
+        let name = mesh.name.clone();
+        let geomery = TriMesh::new(mesh.vertices, mesh.indices)
+            .map(Geomery::TriMesh)
+            .ok_or(ImportError::InvalidMesh(name.clone()))?;
+        shapes.push(ImportedShape { name, geomery });
+    }
+    shapes.extend(proxies);
+    Ok(shapes)
+}
+
+fn fit(kind: Proxy, mesh: &Mesh) -> Option<Geomery> {
+    if mesh.vertices.is_empty() {
+        return None;
+    }
+
+    match kind {
+        Proxy::Convex => ConvexHull::new(&mesh.vertices).map(Geomery::ConvexHull),
+        Proxy::Sphere => {
+            let center = bounds(&mesh.vertices).2.center();
+            let radius = mesh
+                .vertices
+                .iter()
+                .map(|p| (p - center).norm())
+                .fold(0.0, Real::max);
+            Some(Geomery::Sphere(Sphere(
+                Ball::new(radius),
+                Isometry::translation(center.x, center.y, center.z),
+            )))
+        }
+        Proxy::Box => Some(
+            fit_obb(mesh)
+                .map(Geomery::Obb)
+                .unwrap_or_else(|| Geomery::Aabb(bounds(&mesh.vertices))),
+        ),
+    }
+}
+
+fn bounds(points: &[Point3]) -> Aabb {
+    let (mins, maxs) = points
+        .iter()
+        .fold((points[0], points[0]), |(mins, maxs), p| {
+            (mins.inf(p), maxs.sup(p))
+        });
+    Aabb::new(mins, maxs)
+}
+
+// Box axes are taken from the face normals, the first one and the first perpendicular to it.
+fn fit_obb(mesh: &Mesh) -> Option<Obb> {
+    let normals: Vec<Vector3> = mesh
+        .indices
+        .iter()
+        .filter_map(|[a, b, c]| {
+            let [a, b, c] = [a, b, c].map(|i| mesh.vertices[*i as usize]);
+            (b - a).cross(&(c - a)).try_normalize(1.0e-6)
+        })
+        .collect();
+
+    let u = *normals.first()?;
+    let v = *normals.iter().find(|n| n.dot(&u).abs() < 1.0e-3)?;
+    let w = u.cross(&v);
+    let rotation = Rotation3::from_matrix_unchecked(Matrix3::from_columns(&[u, v, w]));
+
+    // Extents along the box axes, in the box frame.
+    let local: Vec<Point3> = mesh
+        .vertices
+        .iter()
+        .map(|p| rotation.inverse_transform_point(p))
+        .collect();
+    let local = bounds(&local).2;
+    let center = rotation * local.center();
+
+    Some(Obb(
+        Cuboid::new(local.half_extents()),
+        Isometry::from_parts(
+            Translation3::from(center.coords),
+            UnitQuaternion::from_rotation_matrix(&rotation),
+        ),
+    ))
+}
+
+#[cfg(test)]
+use crate::export::TestDir;
+
+#[cfg(test)]
+fn test_exporter() -> Exporter {
+    let mut exporter = Exporter::new();
+    exporter
+        .add(
+            "Rock",
+            &Geomery::Obb(Obb::new(
+                Point3::new(0.0, 0.0, 0.0),
+                Vector3::zeros(),
+                Vector3::new(1.0, 1.0, 1.0),
+            )),
+        )
+        .add(
+            "UBX_Rock_01",
+            &Geomery::Obb(Obb::new(
+                Point3::new(1.0, 2.0, 3.0),
+                Vector3::new(0.0, 0.5, 0.0),
+                Vector3::new(1.0, 2.0, 3.0),
+            )),
+        )
+        .add(
+            "USP_Rock_02",
+            &Geomery::Sphere(Sphere(
+                Ball::new(2.0),
+                Isometry::translation(-5.0, 0.0, 0.0),
+            )),
+        )
+        .add(
+            "UCX_Tree",
+            &Geomery::Aabb(Aabb::new(
+                Point3::new(0.0, 0.0, 10.0),
+                Point3::new(1.0, 4.0, 11.0),
+            )),
+        )
+        .add(
+            "Ground",
+            &Geomery::Aabb(Aabb::new(
+                Point3::new(-10.0, -1.0, -10.0),
+                Point3::new(10.0, 0.0, 10.0),
+            )),
+        );
+    exporter
+}
+
+#[cfg(test)]
+fn check_imported(shapes: &[ImportedShape]) {
+    let names: Vec<&str> = shapes.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Ground", "Rock", "Tree"]);
+
+    assert_eq!(shapes[0].geomery.kind(), GeomeryKind::TriMesh);
+
+    let Geomery::Compound(parts) = &shapes[1].geomery else {
+        panic!("expected a compound");
+    };
+    let Geomery::Obb(obb) = &parts[0] else {
+        panic!("expected a box");
+    };
+    let half_extents = obb.0.half_extents;
+    let mut sorted = [half_extents.x, half_extents.y, half_extents.z];
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    for (a, b) in sorted.iter().zip([1.0, 2.0, 3.0]) {
+        assert!((a - b).abs() < 1.0e-4);
+    }
+    assert!((obb.1.translation.vector - Vector3::new(1.0, 2.0, 3.0)).norm() < 1.0e-4);
+
+    let Geomery::Sphere(sphere) = &parts[1] else {
+        panic!("expected a sphere");
+    };
+    assert!((sphere.0.radius - 2.0).abs() < 1.0e-4);
+    assert!((sphere.1.translation.vector - Vector3::new(-5.0, 0.0, 0.0)).norm() < 1.0e-4);
+
+    let Geomery::ConvexHull(hull) = &shapes[2].geomery else {
+        panic!("expected a convex hull");
+    };
+    assert_eq!(hull.0.points().len(), 8);
+}
+
+#[test]
+fn test_base_name() {
+    assert_eq!(base_name("Rock_01"), "Rock");
+    assert_eq!(base_name("Rock.001"), "Rock");
+    assert_eq!(base_name("Rock_01.002"), "Rock");
+    assert_eq!(base_name("Rock_A"), "Rock_A");
+    assert_eq!(base_name("_01"), "_01");
+    assert!(proxy("UCX_Rock").is_some_and(|(kind, base)| kind == Proxy::Convex && base == "Rock"));
+    assert!(proxy("Rock").is_none());
+}
+
+#[test]
+fn test_import_glb() {
+    let dir = TestDir::new("glb");
+    let path = dir.join("scene.glb");
+    test_exporter().save(&path).unwrap();
+    check_imported(&load(&path).unwrap());
+}
+
+#[test]
+fn test_import_obj() {
+    let dir = TestDir::new("obj");
+    let path = dir.join("scene.obj");
+    test_exporter().save(&path).unwrap();
+    check_imported(&load(&path).unwrap());
+}
+
+#[test]
+fn test_import_skips_lines() {
+    let dir = TestDir::new("lines");
+    let mut exporter = Exporter::new();
+    exporter
+        .add(
+            "Rock",
+            &Geomery::Aabb(Aabb::new(Point3::origin(), Point3::new(1.0, 1.0, 1.0))),
+        )
+        .add(
+            "ray",
+            &Geomery::Ray(crate::shape::Ray::new(Point3::origin(), Vector3::x())),
+        )
+        .add_lines("line", &[(Point3::origin(), Point3::new(0.0, 1.0, 0.0))]);
+
+    for file in ["scene.obj", "scene.glb"] {
+        let path = dir.join(file);
+        exporter.save(&path).unwrap();
+        let shapes = load(&path).unwrap();
+        assert_eq!(shapes.len(), 1, "{}", file);
+        assert_eq!(shapes[0].name, "Rock");
+        assert_eq!(shapes[0].geomery.kind(), GeomeryKind::TriMesh);
+    }
+}
+
+#[test]
+fn test_import_errors() {
+    assert!(matches!(
+        load("scene.fbx"),
+        Err(ImportError::Unsupported(ext)) if ext == "fbx"
+    ));
+    assert!(load("missing.obj").is_err());
+    assert!(load("missing.glb").is_err());
+
+    let dir = TestDir::new("errors");
+    let path = dir.join("flat.obj");
+    let mut exporter = Exporter::new();
+    exporter.add(
+        "UCX_Flat",
+        &Geomery::Triangle(crate::shape::Triangle(
+            crate::shape::MTriangle::new(
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ),
+            Isometry::identity(),
+        )),
+    );
+    exporter.save(&path).unwrap();
+    assert!(matches!(
+        load(&path),
+        Err(ImportError::InvalidMesh(name)) if name == "UCX_Flat"
+    ));
+}
@@ -0,0 +1,172 @@
+use crate::shape::{Aabb, Frustum, Obb, Plane, Point3, Ray, Sphere, Triangle};
+use crate::{
+    compute_aabb_and_aabb, compute_aabb_and_frustum, compute_aabb_and_obb, compute_aabb_and_plane,
+    compute_aabb_and_triangle, compute_frustum_and_frustum, compute_obb_and_frustum,
+    compute_obb_and_obb, compute_obb_and_plane, compute_obb_and_triangle,
+    compute_plane_and_frustum, compute_plane_and_triangle, compute_point_and_aabb,
+    compute_point_and_obb, compute_point_and_sphere, compute_ray_and_aabb, compute_ray_and_obb,
+    compute_ray_and_sphere, compute_ray_and_triangle, compute_sphere_and_aabb,
+    compute_sphere_and_frustum, compute_sphere_and_obb, compute_sphere_and_plane,
+    compute_sphere_and_sphere, compute_sphere_and_triangle, compute_triangle_and_frustum,
+    compute_triangle_and_triangle,
+};
+
+#[allow(unused_imports)]
+use crate::shape::{Ball, Isometry, MTriangle, Vector3};
+#[allow(unused_imports)]
+use crate::{try_test_collision, Geomery};
+
+// Statically dispatched collision test, only implemented for the pairs
+// `test_collision` supports, in both orders.
+pub trait Intersects<B> {
+    fn intersects(&self, other: &B) -> bool;
+}
+
+macro_rules! impl_intersects {
+    ($a:ty, $b:ty, $f:path) => {
+        impl Intersects<$b> for $a {
+            fn intersects(&self, other: &$b) -> bool {
+                $f(self, other)
+            }
+        }
+    };
+    ($a:ty, $b:ty, $f:path, reversed) => {
+        impl_intersects!($a, $b, $f);
+
+        impl Intersects<$a> for $b {
+            fn intersects(&self, other: &$a) -> bool {
+                $f(other, self)
+            }
+        }
+    };
+}
+
+impl_intersects!(Point3, Sphere, compute_point_and_sphere, reversed);
+impl_intersects!(Point3, Aabb, compute_point_and_aabb, reversed);
+impl_intersects!(Point3, Obb, compute_point_and_obb, reversed);
+impl_intersects!(Ray, Sphere, compute_ray_and_sphere, reversed);
+impl_intersects!(Ray, Aabb, compute_ray_and_aabb, reversed);
+impl_intersects!(Ray, Triangle, compute_ray_and_triangle, reversed);
+impl_intersects!(Ray, Obb, compute_ray_and_obb, reversed);
+impl_intersects!(Plane, Triangle, compute_plane_and_triangle, reversed);
+impl_intersects!(Sphere, Plane, compute_sphere_and_plane, reversed);
+impl_intersects!(Sphere, Sphere, compute_sphere_and_sphere);
+impl_intersects!(Sphere, Aabb, compute_sphere_and_aabb, reversed);
+impl_intersects!(Sphere, Triangle, compute_sphere_and_triangle, reversed);
+impl_intersects!(Sphere, Frustum, compute_sphere_and_frustum, reversed);
+impl_intersects!(Sphere, Obb, compute_sphere_and_obb, reversed);
+impl_intersects!(Aabb, Plane, compute_aabb_and_plane, reversed);
+impl_intersects!(Aabb, Aabb, compute_aabb_and_aabb);
+impl_intersects!(Aabb, Triangle, compute_aabb_and_triangle, reversed);
+impl_intersects!(Aabb, Frustum, compute_aabb_and_frustum, reversed);
+impl_intersects!(Aabb, Obb, compute_aabb_and_obb, reversed);
+impl_intersects!(Triangle, Triangle, compute_triangle_and_triangle);
+impl_intersects!(Obb, Plane, compute_obb_and_plane, reversed);
+impl_intersects!(Obb, Triangle, compute_obb_and_triangle, reversed);
+impl_intersects!(Obb, Obb, compute_obb_and_obb);
+impl_intersects!(Obb, Frustum, compute_obb_and_frustum, reversed);
+impl_intersects!(Plane, Frustum, compute_plane_and_frustum, reversed);
+impl_intersects!(Triangle, Frustum, compute_triangle_and_frustum, reversed);
+impl_intersects!(Frustum, Frustum, compute_frustum_and_frustum);
+
+#[cfg(test)]
+fn check<A, B>(a: &A, b: &B, ga: Geomery, gb: Geomery)
+where
+    A: Intersects<B>,
+    B: Intersects<A>,
+{
+    let expected = try_test_collision(&ga, &gb)
+        .or_else(|| try_test_collision(&gb, &ga))
+        .unwrap();
+    assert_eq!(a.intersects(b), expected);
+    assert_eq!(b.intersects(a), expected);
+}
+
+#[test]
+fn test_intersects() {
+    let point = Point3::new(0.5, 0.0, 0.0);
+    let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vector3::x());
+    let sphere = Sphere(Ball::new(1.0), Isometry::identity());
+    let aabb = Aabb::new(Point3::new(0.0, -1.0, -1.0), Point3::new(2.0, 1.0, 1.0));
+    let obb = Obb::new(
+        Point3::new(5.0, 0.0, 0.0),
+        Vector3::new(0.0, 0.0, 0.5),
+        Vector3::new(1.0, 1.0, 1.0),
+    );
+    let triangle = Triangle(
+        MTriangle::new(
+            Point3::new(0.0, -1.0, -1.0),
+            Point3::new(0.0, 1.0, -1.0),
+            Point3::new(0.0, 0.0, 1.0),
+        ),
+        Isometry::translation(3.0, 0.0, 0.0),
+    );
+
+    check(
+        &point,
+        &sphere,
+        Geomery::Point(point),
+        Geomery::Sphere(sphere.clone()),
+    );
+    check(
+        &point,
+        &obb,
+        Geomery::Point(point),
+        Geomery::Obb(obb.clone()),
+    );
+    check(&ray, &aabb, Geomery::Ray(ray), Geomery::Aabb(aabb.clone()));
+    check(
+        &ray,
+        &triangle,
+        Geomery::Ray(ray),
+        Geomery::Triangle(triangle.clone()),
+    );
+    check(
+        &sphere,
+        &obb,
+        Geomery::Sphere(sphere.clone()),
+        Geomery::Obb(obb.clone()),
+    );
+    check(
+        &aabb,
+        &triangle,
+        Geomery::Aabb(aabb.clone()),
+        Geomery::Triangle(triangle.clone()),
+    );
+    check(
+        &obb,
+        &triangle,
+        Geomery::Obb(obb.clone()),
+        Geomery::Triangle(triangle.clone()),
+    );
+
+    assert_eq!(point.intersects(&aabb), true);
+    assert_eq!(sphere.intersects(&sphere), true);
+    assert_eq!(obb.intersects(&point), false);
+    assert_eq!(triangle.intersects(&ray), true);
+    assert_eq!(aabb.intersects(&obb), false);
+
+    let frustum = Frustum::from_corners(
+        [
+            Point3::new(1.0, 1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+            Point3::new(-1.0, -1.0, -1.0),
+            Point3::new(1.0, -1.0, -1.0),
+        ],
+        [
+            Point3::new(2.0, 2.0, -5.0),
+            Point3::new(-2.0, 2.0, -5.0),
+            Point3::new(-2.0, -2.0, -5.0),
+            Point3::new(2.0, -2.0, -5.0),
+        ],
+    )
+    .unwrap();
+    check(
+        &obb,
+        &frustum,
+        Geomery::Obb(obb.clone()),
+        Geomery::Frustum(frustum.clone()),
+    );
+    assert_eq!(frustum.intersects(&frustum), true);
+    assert_eq!(triangle.intersects(&frustum), false);
+}
@@ -1,4 +1,35 @@
+#![allow(clippy::bool_assert_comparison)]
+
+#[cfg(not(any(feature = "f32", feature = "f64")))]
+compile_error!("one of the features `f32` or `f64` must be enabled");
+
+// `f64` wins over `f32` so the features stay additive.
+#[cfg(feature = "f64")]
+extern crate parry3d_f64 as parry3d;
+
+pub mod batch;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod cluster;
+#[cfg(feature = "2d")]
+pub mod d2;
+pub mod debug;
+pub mod dispatch;
+#[cfg(feature = "ecs")]
+pub mod ecs;
+pub mod export;
+#[cfg(feature = "import")]
+pub mod import;
+pub mod intersects;
+pub mod occlusion;
+pub mod screen;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod shadow;
 pub mod shape;
+pub mod soa;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 use std::fmt::Debug;
 
@@ -6,12 +37,17 @@ use parry3d::bounding_volume::BoundingVolume;
 use parry3d::{
     math::Isometry,
     query::{PointQuery, RayCast},
+    shape::Shape,
+};
+use shape::{
+    Aabb, ApproxEq, Ball, Contact, ConvexHull, Custom, Frustum, Obb, Plane, Point3,
+    PointProjection, Ray, Real, Sphere, TriMesh, Triangle, Unsupported,
 };
-use shape::{Aabb, Ball, Frustum, Obb, Plane, Point3, Ray, Sphere, Triangle};
 
 #[allow(unused_imports)]
 use crate::shape::{ConvexPolyhedron, HalfSpace, MTriangle, Vector3};
 
+#[derive(Debug, Clone)]
 pub enum Geomery {
     Point(Point3),
     Ray(Ray),
@@ -22,26 +58,246 @@ pub enum Geomery {
     Triangle(Triangle),
     Frustum(Frustum),
     Obb(Obb),
+    TriMesh(TriMesh),
+    ConvexHull(ConvexHull),
+    // Parts are in world space.
+    Compound(Vec<Geomery>),
+    Custom(Custom),
+}
+
+impl PartialEq for Geomery {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Geomery::Point(a), Geomery::Point(b)) => a == b,
+            (Geomery::Ray(a), Geomery::Ray(b)) => a.origin == b.origin && a.dir == b.dir,
+            (Geomery::Plane(a), Geomery::Plane(b)) => a == b,
+            (Geomery::Sphere(a), Geomery::Sphere(b)) => a == b,
+            (Geomery::Aabb(a), Geomery::Aabb(b)) => a == b,
+            (Geomery::Ball(a), Geomery::Ball(b)) => a == b,
+            (Geomery::Triangle(a), Geomery::Triangle(b)) => a == b,
+            (Geomery::Frustum(a), Geomery::Frustum(b)) => a == b,
+            (Geomery::Obb(a), Geomery::Obb(b)) => a == b,
+            (Geomery::TriMesh(a), Geomery::TriMesh(b)) => a == b,
+            (Geomery::ConvexHull(a), Geomery::ConvexHull(b)) => a == b,
+            (Geomery::Compound(a), Geomery::Compound(b)) => a == b,
+            (Geomery::Custom(a), Geomery::Custom(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl ApproxEq for Geomery {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        match (self, other) {
+            (Geomery::Point(a), Geomery::Point(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Ray(a), Geomery::Ray(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Plane(a), Geomery::Plane(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Sphere(a), Geomery::Sphere(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Aabb(a), Geomery::Aabb(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Ball(a), Geomery::Ball(b)) => a.radius.approx_eq(&b.radius, epsilon),
+            (Geomery::Triangle(a), Geomery::Triangle(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Frustum(a), Geomery::Frustum(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Obb(a), Geomery::Obb(b)) => a.approx_eq(b, epsilon),
+            (Geomery::TriMesh(a), Geomery::TriMesh(b)) => a.approx_eq(b, epsilon),
+            (Geomery::ConvexHull(a), Geomery::ConvexHull(b)) => a.approx_eq(b, epsilon),
+            (Geomery::Compound(a), Geomery::Compound(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.approx_eq(b, epsilon))
+            }
+            (Geomery::Custom(a), Geomery::Custom(b)) => a.approx_eq(b, epsilon),
+            _ => false,
+        }
+    }
+}
+
+// Variant of a `Geomery` without its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeomeryKind {
+    Point,
+    Ray,
+    Plane,
+    Sphere,
+    Aabb,
+    Ball,
+    Triangle,
+    Frustum,
+    Obb,
+    TriMesh,
+    ConvexHull,
+    Compound,
+    Custom,
 }
 
-impl Debug for Geomery {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl GeomeryKind {
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Self::Point(_) => f.debug_tuple("Point").finish(),
-            Self::Ray(_) => f.debug_tuple("Ray").finish(),
-            Self::Plane(_) => f.debug_tuple("Plane").finish(),
-            Self::Sphere(_) => f.debug_tuple("Sphere").finish(),
-            Self::Aabb(_) => f.debug_tuple("Aabb").finish(),
-            Self::Ball(_) => f.debug_tuple("Ball").finish(),
-            Self::Triangle(_) => f.debug_tuple("Triangle").finish(),
-            Self::Frustum(_) => f.debug_tuple("Frustum").finish(),
-            Self::Obb(_) => f.debug_tuple("Obb").finish(),
+            GeomeryKind::Point => "Point",
+            GeomeryKind::Ray => "Ray",
+            GeomeryKind::Plane => "Plane",
+            GeomeryKind::Sphere => "Sphere",
+            GeomeryKind::Aabb => "Aabb",
+            GeomeryKind::Ball => "Ball",
+            GeomeryKind::Triangle => "Triangle",
+            GeomeryKind::Frustum => "Frustum",
+            GeomeryKind::Obb => "Obb",
+            GeomeryKind::TriMesh => "TriMesh",
+            GeomeryKind::ConvexHull => "ConvexHull",
+            GeomeryKind::Compound => "Compound",
+            GeomeryKind::Custom => "Custom",
         }
     }
 }
 
+impl std::fmt::Display for GeomeryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Geomery {
+    pub fn kind(&self) -> GeomeryKind {
+        match self {
+            Geomery::Point(_) => GeomeryKind::Point,
+            Geomery::Ray(_) => GeomeryKind::Ray,
+            Geomery::Plane(_) => GeomeryKind::Plane,
+            Geomery::Sphere(_) => GeomeryKind::Sphere,
+            Geomery::Aabb(_) => GeomeryKind::Aabb,
+            Geomery::Ball(_) => GeomeryKind::Ball,
+            Geomery::Triangle(_) => GeomeryKind::Triangle,
+            Geomery::Frustum(_) => GeomeryKind::Frustum,
+            Geomery::Obb(_) => GeomeryKind::Obb,
+            Geomery::TriMesh(_) => GeomeryKind::TriMesh,
+            Geomery::ConvexHull(_) => GeomeryKind::ConvexHull,
+            Geomery::Compound(_) => GeomeryKind::Compound,
+            Geomery::Custom(_) => GeomeryKind::Custom,
+        }
+    }
+}
+
+// One of each serializable variant, shared by the serde and dispatch tests.
+#[cfg(test)]
+pub(crate) fn test_geomeries() -> Vec<Geomery> {
+    vec![
+        Geomery::Point(Point3::new(1.0, 2.0, 3.0)),
+        Geomery::Ray(Ray::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 1.0),
+        )),
+        Geomery::Plane(Plane(
+            HalfSpace::new(Vector3::y_axis()),
+            Isometry::translation(0.0, -1.0, 0.0),
+        )),
+        Geomery::Sphere(Sphere(Ball::new(1.5), Isometry::translation(1.0, 2.0, 3.0))),
+        Geomery::Aabb(Aabb::new(
+            Point3::new(-1.0, -2.0, -3.0),
+            Point3::new(1.0, 2.0, 3.0),
+        )),
+        Geomery::Ball(Ball::new(0.5)),
+        Geomery::Triangle(Triangle(
+            MTriangle::new(
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ),
+            Isometry::rotation(Vector3::new(1.0, 0.0, 0.0)),
+        )),
+        Geomery::Frustum(Frustum(
+            shape::test_frustum().0,
+            Isometry::translation(0.0, 0.0, -1.0),
+        )),
+        Geomery::Obb(Obb::new(
+            Point3::new(1.0, 1.0, 1.0),
+            Vector3::new(1.0, 1.0, 0.0),
+            Vector3::new(1.0, 2.0, 3.0),
+        )),
+        Geomery::TriMesh(
+            TriMesh::new(
+                vec![
+                    Point3::new(0.0, 0.0, 0.0),
+                    Point3::new(1.0, 0.0, 0.0),
+                    Point3::new(0.0, 1.0, 0.0),
+                    Point3::new(0.0, 0.0, 1.0),
+                ],
+                vec![[0, 1, 2], [0, 1, 3]],
+            )
+            .unwrap(),
+        ),
+        Geomery::ConvexHull(
+            ConvexHull::new(&[
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+                Point3::new(0.0, 0.0, 1.0),
+            ])
+            .unwrap(),
+        ),
+        Geomery::Compound(vec![
+            Geomery::Point(Point3::new(1.0, 2.0, 3.0)),
+            Geomery::Ball(Ball::new(0.5)),
+        ]),
+    ]
+}
+
+#[test]
+fn test_geomery_debug() {
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.5), Isometry::translation(1.0, 2.0, 3.0)));
+    let debug = format!("{:?}", sphere);
+
+    assert!(debug.starts_with("Sphere(Sphere(Ball { radius: 1.5 }"));
+    assert!(debug.contains("1.0, 2.0, 3.0"));
+    assert_eq!(sphere.kind(), GeomeryKind::Sphere);
+    assert_eq!(sphere.kind().to_string(), "Sphere");
+}
+
+#[test]
+fn test_geomery_eq() {
+    let obb = Geomery::Obb(Obb::new(
+        Point3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, 0.5, 0.0),
+        Vector3::new(1.0, 2.0, 3.0),
+    ));
+    assert_eq!(obb.clone(), obb);
+    assert_ne!(obb, Geomery::Point(Point3::new(1.0, 0.0, 0.0)));
+
+    let ray = Geomery::Ray(Ray::new(Point3::origin(), Vector3::x()));
+    assert_eq!(ray.clone(), ray);
+
+    let moved = Geomery::Obb(Obb::new(
+        Point3::new(1.0 + 1.0e-4, 0.0, 0.0),
+        Vector3::new(0.0, 0.5 + 1.0e-4, 0.0),
+        Vector3::new(1.0, 2.0, 3.0),
+    ));
+    assert_ne!(moved, obb);
+    assert_eq!(moved.approx_eq(&obb, 1.0e-3), true);
+    assert_eq!(moved.approx_eq(&obb, 1.0e-5), false);
+}
+
+#[test]
+fn test_frustum_approx_eq() {
+    let (near, far) = shape::test_frustum_corners();
+    let points = [near, far].concat();
+    let mut reversed = points.clone();
+    reversed.reverse();
+
+    let f1 = Frustum::from_points(&points).unwrap();
+    let f2 = Frustum::from_points(&reversed).unwrap();
+    assert_eq!(f1.approx_eq(&f2, 1.0e-6), true);
+
+    let f3 = Frustum::from_corners(near.map(|p| p * 2.0), far.map(|p| p * 2.0)).unwrap();
+    assert_eq!(f1.approx_eq(&f3, 1.0e-6), false);
+}
+
 pub fn test_collision(geomery1: &Geomery, geomery2: &Geomery) -> bool {
-    match (geomery1, geomery2) {
+    match try_test_collision(geomery1, geomery2) {
+        Some(r) => r,
+        None => panic!(
+            "not support geomery type test collision!!! g1: {:?}, g2: {:?}",
+            geomery1, geomery2
+        ),
+    }
+}
+
+pub fn try_test_collision(geomery1: &Geomery, geomery2: &Geomery) -> Option<bool> {
+    let r = match (geomery1, geomery2) {
         (Geomery::Point(p), Geomery::Sphere(s)) => compute_point_and_sphere(p, s),
         (Geomery::Point(p), Geomery::Aabb(ab)) => compute_point_and_aabb(p, ab),
         (Geomery::Point(p), Geomery::Obb(obb)) => compute_point_and_obb(p, obb),
@@ -65,13 +321,508 @@ pub fn test_collision(geomery1: &Geomery, geomery2: &Geomery) -> bool {
         (Geomery::Obb(obb), Geomery::Plane(p)) => compute_obb_and_plane(obb, p),
         (Geomery::Obb(obb), Geomery::Triangle(t)) => compute_obb_and_triangle(obb, t),
         (Geomery::Obb(ob1), Geomery::Obb(ob2)) => compute_obb_and_obb(ob1, ob2),
-        _ => panic!(
-            "not support geomery type test collision!!! g1: {:?}, g2: {:?}",
-            geomery1, geomery2
+        (Geomery::Obb(obb), Geomery::Frustum(f)) => compute_obb_and_frustum(obb, f),
+        (Geomery::Plane(p), Geomery::Frustum(f)) => compute_plane_and_frustum(p, f),
+        (Geomery::Triangle(t), Geomery::Frustum(f)) => compute_triangle_and_frustum(t, f),
+        (Geomery::Frustum(f1), Geomery::Frustum(f2)) => compute_frustum_and_frustum(f1, f2),
+        (Geomery::Compound(parts), g) => {
+            return compute_compound(parts, |part| try_test_collision(part, g))
+        }
+        (g, Geomery::Compound(parts)) => {
+            return compute_compound(parts, |part| try_test_collision(g, part))
+        }
+        (
+            Geomery::Ray(r),
+            g @ (Geomery::TriMesh(_) | Geomery::ConvexHull(_) | Geomery::Custom(_)),
+        ) => raycast(r, g, Real::MAX).is_some(),
+        (Geomery::TriMesh(_) | Geomery::ConvexHull(_) | Geomery::Custom(_), _)
+        | (_, Geomery::TriMesh(_) | Geomery::ConvexHull(_) | Geomery::Custom(_)) => {
+            return shape_intersection_test(geomery1, geomery2)
+        }
+        _ => return None,
+    };
+
+    Some(r)
+}
+
+// Any part hit is a hit, otherwise unsupported parts make the whole test unsupported.
+pub(crate) fn compute_compound(
+    parts: &[Geomery],
+    f: impl Fn(&Geomery) -> Option<bool>,
+) -> Option<bool> {
+    let mut supported = true;
+    for part in parts {
+        match f(part) {
+            Some(true) => return Some(true),
+            Some(false) => {}
+            None => supported = false,
+        }
+    }
+    supported.then_some(false)
+}
+
+// Planes are two sided, a shape touches one when it reaches both of its half spaces.
+fn shape_intersection_test(geomery1: &Geomery, geomery2: &Geomery) -> Option<bool> {
+    let ((m1, s1), (m2, s2)) = (as_shape(geomery1)?, as_shape(geomery2)?);
+    let test =
+        |s1: &dyn Shape, s2: &dyn Shape| parry3d::query::intersection_test(&m1, s1, &m2, s2).ok();
+
+    match (geomery1, geomery2) {
+        (Geomery::Plane(p), _) => {
+            let [back, front] = p.half_spaces();
+            Some(test(&back, s2)? && test(&front, s2)?)
+        }
+        (_, Geomery::Plane(p)) => {
+            let [back, front] = p.half_spaces();
+            Some(test(s1, &back)? && test(s1, &front)?)
+        }
+        _ => test(s1, s2),
+    }
+}
+
+// Points are treated as zero radius balls, rays have no volume.
+const POINT_SHAPE: Ball = Ball { radius: 0.0 };
+
+pub(crate) fn as_shape(geomery: &Geomery) -> Option<(Isometry<Real>, &dyn Shape)> {
+    match geomery {
+        Geomery::Point(p) => Some((Isometry::translation(p.x, p.y, p.z), &POINT_SHAPE)),
+        Geomery::Ray(_) => None,
+        Geomery::Plane(p) => Some((p.1, &p.0)),
+        Geomery::Sphere(s) => Some((s.1, &s.0)),
+        Geomery::Aabb(ab) => Some((ab.1, &ab.0)),
+        Geomery::Ball(b) => Some((Isometry::identity(), b)),
+        Geomery::Triangle(t) => Some((t.1, &t.0)),
+        Geomery::Frustum(f) => Some((f.1, &f.0)),
+        Geomery::Obb(obb) => Some((obb.1, &obb.0)),
+        Geomery::TriMesh(t) => Some((t.1, &t.0)),
+        Geomery::ConvexHull(c) => Some((c.1, &c.0)),
+        Geomery::Custom(c) => Some((c.1, &c.0)),
+        Geomery::Compound(_) => None,
+    }
+}
+
+pub fn distance(geomery1: &Geomery, geomery2: &Geomery) -> Result<Real, Unsupported> {
+    if let (Geomery::Compound(parts), g) | (g, Geomery::Compound(parts)) = (geomery1, geomery2) {
+        return parts
+            .iter()
+            .try_fold(Real::MAX, |d, part| Ok(d.min(distance(part, g)?)));
+    }
+
+    let ((m1, s1), (m2, s2)) = match (as_shape(geomery1), as_shape(geomery2)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Err(Unsupported),
+    };
+
+    // Each half space of a plane is at zero distance from shapes on its side,
+    // so the farther one gives the distance to the plane itself.
+    match (geomery1, geomery2) {
+        (Geomery::Plane(p), _) => p.half_spaces().iter().try_fold(0.0, |d, h| {
+            Ok(Real::max(d, parry3d::query::distance(&m1, h, &m2, s2)?))
+        }),
+        (_, Geomery::Plane(p)) => p.half_spaces().iter().try_fold(0.0, |d, h| {
+            Ok(Real::max(d, parry3d::query::distance(&m1, s1, &m2, h)?))
+        }),
+        _ => parry3d::query::distance(&m1, s1, &m2, s2),
+    }
+}
+
+#[test]
+fn test_distance() {
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::identity()));
+
+    let aabb = Geomery::Aabb(Aabb::new(
+        Point3::new(3.0, -1.0, -1.0),
+        Point3::new(4.0, 1.0, 1.0),
+    ));
+    assert_eq!(distance(&sphere, &aabb), Ok(2.0));
+
+    let point = Geomery::Point(Point3::new(0.0, 5.0, 0.0));
+    assert_eq!(distance(&point, &sphere), Ok(4.0));
+
+    let ray = Geomery::Ray(Ray::new(Point3::origin(), Vector3::x()));
+    assert_eq!(distance(&ray, &sphere), Err(Unsupported));
+
+    let plane = Geomery::Plane(Plane::new(Point3::new(0.0, 3.0, 0.0), Vector3::y()).unwrap());
+    assert_eq!(distance(&sphere, &plane), Ok(2.0));
+    let plane = Geomery::Plane(Plane::new(Point3::new(0.0, -3.0, 0.0), Vector3::y()).unwrap());
+    assert_eq!(distance(&plane, &sphere), Ok(2.0));
+}
+
+pub fn contact(
+    geomery1: &Geomery,
+    geomery2: &Geomery,
+    prediction: Real,
+) -> Result<Option<Contact>, Unsupported> {
+    let ((m1, s1), (m2, s2)) = match (as_shape(geomery1), as_shape(geomery2)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Err(Unsupported),
+    };
+
+    // Planes push shapes out on the side of their center.
+    let side = |p: &Plane, m: &Isometry<Real>| {
+        let [back, front] = p.half_spaces();
+        if p.signed_distance(&Point3::from(m.translation.vector)) >= 0.0 {
+            back
+        } else {
+            front
+        }
+    };
+    match (geomery1, geomery2) {
+        (Geomery::Plane(p), _) => parry3d::query::contact(&m1, &side(p, &m2), &m2, s2, prediction),
+        (_, Geomery::Plane(p)) => parry3d::query::contact(&m1, s1, &m2, &side(p, &m1), prediction),
+        _ => parry3d::query::contact(&m1, s1, &m2, s2, prediction),
+    }
+}
+
+#[test]
+fn test_contact() {
+    let sphere0 = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::identity()));
+
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(1.5, 0.0, 0.0)));
+    let c = contact(&sphere0, &sphere, 0.0).unwrap().unwrap();
+    assert_eq!(c.dist, -0.5);
+    assert_eq!(c.normal1.into_inner(), Vector3::x());
+    assert_eq!(c.point1, Point3::new(1.0, 0.0, 0.0));
+
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(3.0, 0.0, 0.0)));
+    assert_eq!(contact(&sphere0, &sphere, 0.0).unwrap().is_none(), true);
+    assert_eq!(contact(&sphere0, &sphere, 2.0).unwrap().is_some(), true);
+
+    let plane = Geomery::Plane(Plane::new(Point3::new(0.0, 0.5, 0.0), Vector3::y()).unwrap());
+    let c = contact(&plane, &sphere0, 0.0).unwrap().unwrap();
+    assert_eq!(c.normal1.into_inner(), -Vector3::y());
+    assert_eq!(c.dist, -0.5);
+    let c = contact(&sphere0, &plane, 0.0).unwrap().unwrap();
+    assert_eq!(c.normal1.into_inner(), Vector3::y());
+}
+
+#[test]
+fn test_try_test_collision() {
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::identity()));
+    let point = Geomery::Point(Point3::origin());
+
+    assert_eq!(try_test_collision(&point, &sphere), Some(true));
+    assert_eq!(try_test_collision(&sphere, &point), None);
+
+    let hull = Geomery::ConvexHull(
+        ConvexHull::new(&[
+            Point3::new(0.0, -5.0, 0.0),
+            Point3::new(1.0, -5.0, 0.0),
+            Point3::new(0.0, -4.0, 0.0),
+            Point3::new(0.0, -5.0, 1.0),
+        ])
+        .unwrap(),
+    );
+    let plane = Geomery::Plane(Plane::new(Point3::origin(), Vector3::y()).unwrap());
+    assert_eq!(try_test_collision(&hull, &plane), Some(false));
+    let plane = Geomery::Plane(Plane::new(Point3::new(0.0, -4.5, 0.0), Vector3::y()).unwrap());
+    assert_eq!(try_test_collision(&plane, &hull), Some(true));
+}
+
+pub fn raycast(ray: &Ray, geomery: &Geomery, max_toi: Real) -> Option<Real> {
+    match geomery {
+        Geomery::Plane(p) => p.cast_ray(ray, max_toi),
+        Geomery::Sphere(s) => s.0.cast_ray(&s.1, ray, max_toi, true),
+        Geomery::Aabb(ab) => ab.2.cast_ray(&Isometry::identity(), ray, max_toi, true),
+        Geomery::Ball(b) => b.cast_ray(&Isometry::identity(), ray, max_toi, true),
+        Geomery::Triangle(t) => t.0.cast_ray(&t.1, ray, max_toi, true),
+        Geomery::Frustum(f) => f.0.cast_ray(&f.1, ray, max_toi, true),
+        Geomery::Obb(obb) => obb.0.cast_ray(&obb.1, ray, max_toi, true),
+        Geomery::TriMesh(t) => t.0.cast_ray(&t.1, ray, max_toi, true),
+        Geomery::ConvexHull(c) => c.0.cast_ray(&c.1, ray, max_toi, true),
+        Geomery::Custom(c) => c.0.cast_ray(&c.1, ray, max_toi, true),
+        Geomery::Compound(parts) => parts
+            .iter()
+            .filter_map(|part| raycast(ray, part, max_toi))
+            .min_by(|a, b| a.total_cmp(b)),
+        Geomery::Point(_) | Geomery::Ray(_) => None,
+    }
+}
+
+#[test]
+fn test_raycast() {
+    let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(3.0, 0.0, 0.0)));
+    assert_eq!(raycast(&ray, &sphere, Real::MAX), Some(2.0));
+    assert_eq!(raycast(&ray, &sphere, 1.0), None);
+
+    let aabb = Geomery::Aabb(Aabb::new(
+        Point3::new(-1.0, -1.0, -1.0),
+        Point3::new(1.0, 1.0, 1.0),
+    ));
+    assert_eq!(raycast(&ray, &aabb, Real::MAX), Some(0.0));
+
+    let point = Geomery::Point(Point3::new(1.0, 0.0, 0.0));
+    assert_eq!(raycast(&ray, &point, Real::MAX), None);
+
+    let plane = Geomery::Plane(Plane::new(Point3::new(4.0, 0.0, 0.0), Vector3::x()).unwrap());
+    assert_eq!(raycast(&ray, &plane, Real::MAX), Some(4.0));
+}
+
+// Points, rays and planes have no volume, they contain the points lying on them up to rounding.
+fn on_surface(proj: Point3, point: &Point3) -> PointProjection {
+    let tolerance = Real::EPSILON * Real::max(1.0, point.coords.amax());
+    PointProjection::new((proj - point).amax() <= tolerance, proj)
+}
+
+// Closest point on the surface, `is_inside` is set for points in solids or on
+// surfaces. Only an empty compound has no projection.
+pub fn project_point(geomery: &Geomery, point: &Point3) -> Option<PointProjection> {
+    let proj = match geomery {
+        Geomery::Point(p) => on_surface(*p, point),
+        Geomery::Ray(r) => {
+            let len = r.dir.norm_squared();
+            let t = if len > 0.0 {
+                Real::max(0.0, r.dir.dot(&(point - r.origin)) / len)
+            } else {
+                0.0
+            };
+            on_surface(r.point_at(t), point)
+        }
+        Geomery::Plane(p) => on_surface(p.project_point(point), point),
+        Geomery::Compound(parts) => {
+            let projs: Vec<PointProjection> = parts
+                .iter()
+                .filter_map(|part| project_point(part, point))
+                .collect();
+            let is_inside = projs.iter().any(|proj| proj.is_inside);
+            let closest = projs.into_iter().min_by(|a, b| {
+                let (a, b) = (a.point - point, b.point - point);
+                a.norm_squared().total_cmp(&b.norm_squared())
+            })?;
+            PointProjection::new(is_inside, closest.point)
+        }
+        _ => {
+            let (m, s) = as_shape(geomery)?;
+            s.project_point(&m, point, false)
+        }
+    };
+
+    Some(proj)
+}
+
+pub fn contains_point(geomery: &Geomery, point: &Point3) -> bool {
+    match geomery {
+        Geomery::Point(_) | Geomery::Ray(_) | Geomery::Plane(_) => {
+            project_point(geomery, point).is_some_and(|proj| proj.is_inside)
+        }
+        Geomery::Compound(parts) => parts.iter().any(|part| contains_point(part, point)),
+        _ => as_shape(geomery).is_some_and(|(m, s)| s.contains_point(&m, point)),
+    }
+}
+
+#[test]
+fn test_project_point() {
+    let point = Point3::new(3.0, 0.5, 0.0);
+
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(1.0, 0.5, 0.0)));
+    let proj = project_point(&sphere, &point).unwrap();
+    assert_eq!(proj.is_inside, false);
+    assert_eq!(proj.point, Point3::new(2.0, 0.5, 0.0));
+
+    // Points inside are projected on the surface.
+    let aabb = Geomery::Aabb(Aabb::new(
+        Point3::new(0.0, -1.0, -1.0),
+        Point3::new(4.0, 1.0, 1.0),
+    ));
+    let proj = project_point(&aabb, &point).unwrap();
+    assert_eq!(proj.is_inside, true);
+    assert_eq!(proj.point, Point3::new(3.0, 1.0, 0.0));
+
+    let triangle = Geomery::Triangle(Triangle(
+        MTriangle::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(0.0, 2.0, 0.0),
+        ),
+        Isometry::translation(0.0, 0.0, 1.0),
+    ));
+    let proj = project_point(&triangle, &Point3::new(0.5, 0.5, 3.0)).unwrap();
+    assert_eq!(proj.is_inside, false);
+    assert_eq!(proj.point, Point3::new(0.5, 0.5, 1.0));
+    let proj = project_point(&triangle, &Point3::new(0.5, 0.5, 1.0)).unwrap();
+    assert_eq!(proj.is_inside, true);
+
+    let plane = Geomery::Plane(Plane::new(Point3::new(0.0, 2.0, 0.0), Vector3::y()).unwrap());
+    let proj = project_point(&plane, &point).unwrap();
+    assert_eq!(proj.is_inside, false);
+    assert_eq!(proj.point, Point3::new(3.0, 2.0, 0.0));
+
+    let ray = Geomery::Ray(Ray::new(Point3::origin(), Vector3::new(2.0, 0.0, 0.0)));
+    let proj = project_point(&ray, &point).unwrap();
+    assert_eq!(proj.is_inside, false);
+    assert_eq!(proj.point, Point3::new(3.0, 0.0, 0.0));
+    let proj = project_point(&ray, &Point3::new(-3.0, 1.0, 0.0)).unwrap();
+    assert_eq!(proj.point, Point3::origin());
+
+    let frustum = Geomery::Frustum(
+        Frustum::from_corners(
+            [
+                Point3::new(1.0, 1.0, -1.0),
+                Point3::new(-1.0, 1.0, -1.0),
+                Point3::new(-1.0, -1.0, -1.0),
+                Point3::new(1.0, -1.0, -1.0),
+            ],
+            [
+                Point3::new(2.0, 2.0, -5.0),
+                Point3::new(-2.0, 2.0, -5.0),
+                Point3::new(-2.0, -2.0, -5.0),
+                Point3::new(2.0, -2.0, -5.0),
+            ],
+        )
+        .unwrap(),
+    );
+    let proj = project_point(&frustum, &Point3::origin()).unwrap();
+    assert_eq!(proj.is_inside, false);
+    assert!(proj.point.approx_eq(&Point3::new(0.0, 0.0, -1.0), 1.0e-5));
+
+    let compound = Geomery::Compound(vec![sphere.clone(), plane.clone()]);
+    let proj = project_point(&compound, &point).unwrap();
+    assert_eq!(proj.point, Point3::new(2.0, 0.5, 0.0));
+    assert_eq!(
+        project_point(&Geomery::Compound(Vec::new()), &point).is_none(),
+        true
+    );
+}
+
+#[test]
+fn test_contains_point() {
+    let geomeries = [
+        Geomery::Point(Point3::new(1.0, 0.0, 0.0)),
+        Geomery::Ray(Ray::new(Point3::origin(), Vector3::x())),
+        Geomery::Plane(Plane::new(Point3::origin(), Vector3::y()).unwrap()),
+        Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::identity())),
+        Geomery::Aabb(Aabb::new(
+            Point3::new(-1.0, -1.0, -1.0),
+            Point3::new(1.0, 1.0, 1.0),
+        )),
+        Geomery::Ball(Ball::new(2.0)),
+        Geomery::Triangle(Triangle(
+            MTriangle::new(
+                Point3::new(0.0, 0.0, -1.0),
+                Point3::new(2.0, 0.0, -1.0),
+                Point3::new(0.0, 0.0, 2.0),
+            ),
+            Isometry::identity(),
+        )),
+        Geomery::Obb(Obb::new(
+            Point3::origin(),
+            Vector3::new(0.0, 0.0, 0.5),
+            Vector3::new(1.0, 1.0, 1.0),
+        )),
+        Geomery::ConvexHull(
+            ConvexHull::new(&[
+                Point3::new(0.0, -1.0, -1.0),
+                Point3::new(4.0, -1.0, -1.0),
+                Point3::new(0.0, 4.0, -1.0),
+                Point3::new(0.0, -1.0, 4.0),
+            ])
+            .unwrap(),
         ),
+        Geomery::Compound(vec![
+            Geomery::Point(Point3::new(5.0, 0.0, 0.0)),
+            Geomery::Sphere(Sphere(Ball::new(0.5), Isometry::translation(1.0, 0.0, 0.0))),
+        ]),
+    ];
+
+    let point = Point3::new(1.0, 0.0, 0.0);
+    for g in &geomeries {
+        assert_eq!(contains_point(g, &point), true, "{}", g.kind());
+        assert_eq!(
+            project_point(g, &point).unwrap().is_inside,
+            true,
+            "{}",
+            g.kind()
+        );
+    }
+
+    let point = Point3::new(1.0, 5.0, 0.5);
+    for g in &geomeries {
+        assert_eq!(contains_point(g, &point), false, "{}", g.kind());
+        assert_eq!(
+            project_point(g, &point).unwrap().is_inside,
+            false,
+            "{}",
+            g.kind()
+        );
+    }
+}
+
+// Box with rounded edges, given only by its support function.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+struct RoundBox(Vector3, Real);
+
+#[cfg(test)]
+impl shape::SupportShape for RoundBox {
+    fn local_support_point(&self, dir: &Vector3) -> Point3 {
+        let corner = dir.zip_map(&self.0, |d, h| if d < 0.0 { -h } else { h });
+        let round = dir
+            .try_normalize(Real::EPSILON)
+            .unwrap_or_else(Vector3::zeros)
+            * self.1;
+        Point3::from(corner + round)
     }
 }
 
+#[test]
+fn test_custom() {
+    let custom = Geomery::Custom(Custom::new(
+        RoundBox(Vector3::new(1.0, 1.0, 1.0), 0.5),
+        Isometry::translation(5.0, 0.0, 0.0),
+    ));
+    assert_eq!(custom.clone(), custom);
+    // Shapes are only known by their support function, separate instances never compare equal.
+    let other = Geomery::Custom(Custom::new(
+        RoundBox(Vector3::new(1.0, 1.0, 1.0), 0.5),
+        Isometry::translation(5.0, 0.0, 0.0),
+    ));
+    assert_ne!(other, custom);
+    assert_eq!(other.approx_eq(&custom, 1.0), false);
+    assert_eq!(custom.kind(), GeomeryKind::Custom);
+    assert!(!debug::debug_triangles(&custom).is_empty());
+    for (a, _) in debug::debug_lines(&custom) {
+        assert!((a.x - 5.0).abs() <= 1.5 + 1.0e-4);
+    }
+
+    // Flat shapes have no hull, they draw their bounding box and no faces.
+    let flat = Geomery::Custom(Custom::new(
+        RoundBox(Vector3::new(1.0, 1.0, 0.0), 0.0),
+        Isometry::translation(5.0, 0.0, 0.0),
+    ));
+    assert_eq!(debug::debug_triangles(&flat).is_empty(), true);
+    assert_eq!(debug::debug_lines(&flat).len(), 12);
+    let point = Geomery::Custom(Custom::new(
+        RoundBox(Vector3::zeros(), 0.0),
+        Isometry::identity(),
+    ));
+    assert_eq!(debug::debug_triangles(&point).is_empty(), true);
+    assert_eq!(debug::debug_lines(&point).len(), 12);
+
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(8.0, 0.0, 0.0)));
+    assert_eq!(test_collision(&custom, &sphere), false);
+    assert_eq!(test_collision(&sphere, &custom), false);
+    assert!((distance(&custom, &sphere).unwrap() - 0.5).abs() < 1.0e-3);
+
+    let point = Geomery::Point(Point3::new(6.4, 0.0, 0.0));
+    assert_eq!(test_collision(&point, &custom), true);
+    let point = Geomery::Point(Point3::new(6.4, 1.4, 0.0));
+    assert_eq!(test_collision(&point, &custom), false);
+
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.0), Isometry::translation(7.0, 0.0, 0.0)));
+    let c = contact(&custom, &sphere, 0.0).unwrap().unwrap();
+    assert!((c.dist + 0.5).abs() < 1.0e-3);
+    assert!((c.normal1.into_inner() - Vector3::x()).norm() < 1.0e-3);
+
+    let ray = Ray::new(Point3::origin(), Vector3::x());
+    assert!((raycast(&ray, &custom, Real::MAX).unwrap() - 3.5).abs() < 1.0e-3);
+    assert_eq!(
+        test_collision(&Geomery::Ray(ray), &Geomery::Compound(vec![custom])),
+        true
+    );
+}
+
 pub fn compute_point_and_sphere(point: &Point3, sphere: &Sphere) -> bool {
     sphere.0.contains_point(&sphere.1, point)
 }
@@ -90,6 +841,18 @@ fn test_point_and_sphere() {
     assert_eq!(compute_point_and_sphere(&point, &sphere), false);
 }
 
+#[cfg(feature = "f64")]
+#[test]
+fn test_point_and_sphere_far_from_origin() {
+    let sphere = Sphere(Ball::new(0.5), Isometry::translation(1.0e9, 0.0, 0.0));
+
+    let point = Point3::new(1.0e9 + 0.25, 0.0, 0.0);
+    assert_eq!(compute_point_and_sphere(&point, &sphere), true);
+
+    let point = Point3::new(1.0e9 + 0.75, 0.0, 0.0);
+    assert_eq!(compute_point_and_sphere(&point, &sphere), false);
+}
+
 pub fn compute_point_and_aabb(point: &Point3, aabb: &Aabb) -> bool {
     aabb.2.contains_point(&Isometry::identity(), point)
 }
@@ -140,12 +903,12 @@ fn test_point_and_obb() {
 
 pub fn compute_line_and_sphere(line: &Ray, sphere: &Sphere) -> bool {
     let ray = line;
-    if sphere.0.intersects_ray(&sphere.1, ray, f32::MAX) {
+    if sphere.0.intersects_ray(&sphere.1, ray, Real::MAX) {
         return true;
     }
 
     let reverse_ray = Ray::new(line.origin, -line.dir);
-    sphere.0.intersects_ray(&sphere.1, &reverse_ray, f32::MAX)
+    sphere.0.intersects_ray(&sphere.1, &reverse_ray, Real::MAX)
 }
 
 #[test]
@@ -167,13 +930,13 @@ fn test_line_and_sphere() {
 
 pub fn compute_line_and_aabb(line: &Ray, aabb: &Aabb) -> bool {
     let ray = line;
-    if aabb.2.intersects_ray(&Isometry::identity(), ray, f32::MAX) {
+    if aabb.2.intersects_ray(&Isometry::identity(), ray, Real::MAX) {
         return true;
     }
 
     let reverse_ray = Ray::new(line.origin, -line.dir);
     aabb.2
-        .intersects_ray(&Isometry::identity(), &reverse_ray, f32::MAX)
+        .intersects_ray(&Isometry::identity(), &reverse_ray, Real::MAX)
 }
 
 #[test]
@@ -192,12 +955,12 @@ fn test_line_and_aabb() {
 
 pub fn compute_line_and_obb(line: &Ray, obb: &Obb) -> bool {
     let ray = line;
-    if obb.0.intersects_ray(&obb.1, ray, f32::MAX) {
+    if obb.0.intersects_ray(&obb.1, ray, Real::MAX) {
         return true;
     }
 
     let reverse_ray = Ray::new(line.origin, -line.dir);
-    obb.0.intersects_ray(&obb.1, &reverse_ray, f32::MAX)
+    obb.0.intersects_ray(&obb.1, &reverse_ray, Real::MAX)
 }
 
 #[test]
@@ -228,12 +991,12 @@ fn test_line_and_obb() {
 
 pub fn compute_line_and_triangle(line: &Ray, tri: &Triangle) -> bool {
     let ray = line;
-    if tri.0.intersects_ray(&tri.1, ray, f32::MAX) {
+    if tri.0.intersects_ray(&tri.1, ray, Real::MAX) {
         return true;
     }
 
     let reverse_ray = Ray::new(line.origin, -line.dir);
-    tri.0.intersects_ray(&tri.1, &reverse_ray, f32::MAX)
+    tri.0.intersects_ray(&tri.1, &reverse_ray, Real::MAX)
 }
 
 #[test]
@@ -272,7 +1035,7 @@ fn test_line_and_triangle() {
 }
 
 pub fn compute_ray_and_sphere(ray: &Ray, sphere: &Sphere) -> bool {
-    sphere.0.intersects_ray(&sphere.1, ray, f32::MAX)
+    sphere.0.intersects_ray(&sphere.1, ray, Real::MAX)
 }
 
 #[test]
@@ -290,7 +1053,7 @@ fn test_ray_and_sphere() {
 }
 
 pub fn compute_ray_and_aabb(ray: &Ray, aabb: &Aabb) -> bool {
-    aabb.2.intersects_ray(&Isometry::identity(), ray, f32::MAX)
+    aabb.2.intersects_ray(&Isometry::identity(), ray, Real::MAX)
 }
 
 #[test]
@@ -308,7 +1071,7 @@ fn test_ray_and_aabb() {
 }
 
 pub fn compute_ray_and_obb(ray: &Ray, obb: &Obb) -> bool {
-    obb.0.intersects_ray(&obb.1, ray, f32::MAX)
+    obb.0.intersects_ray(&obb.1, ray, Real::MAX)
 }
 
 #[test]
@@ -338,7 +1101,7 @@ fn test_ray_and_obb() {
 }
 
 pub fn compute_ray_and_triangle(ray: &Ray, tri: &Triangle) -> bool {
-    tri.0.intersects_ray(&tri.1, ray, f32::MAX)
+    tri.0.intersects_ray(&tri.1, ray, Real::MAX)
 }
 
 #[test]
@@ -443,6 +1206,12 @@ fn test_sphere_and_obb() {
 }
 
 pub fn compute_sphere_and_plane(sphere: &Sphere, plane: &Plane) -> bool {
+    let center = Point3::from(sphere.1.translation.vector);
+    plane.signed_distance(&center).abs() <= sphere.0.radius
+}
+
+// Anything behind the plane touches its half space.
+pub fn compute_sphere_and_half_space(sphere: &Sphere, plane: &Plane) -> bool {
     parry3d::query::intersection_test(&sphere.1, &sphere.0, &plane.1, &plane.0).unwrap()
 }
 
@@ -464,6 +1233,10 @@ fn test_sphere_and_plane() {
         Isometry::translation(0.0, -2.0, 0.0),
     );
     assert_eq!(compute_sphere_and_plane(&sphere, &plane), false);
+
+    let plane = Plane::new(Point3::new(0.0, 10.0, 0.0), Vector3::y()).unwrap();
+    assert_eq!(compute_sphere_and_plane(&sphere, &plane), false);
+    assert_eq!(compute_sphere_and_half_space(&sphere, &plane), true);
 }
 
 pub fn compute_sphere_and_triangle(sphere: &Sphere, tri: &Triangle) -> bool {
@@ -515,7 +1288,7 @@ fn test_sphere_and_frustum() {
 
     let points = vec![
         // near
-        Point3::new(2.0f32, 1.0, 0.0),
+        Point3::new(2.0, 1.0, 0.0),
         Point3::new(-2.0, 1.0, 0.0),
         Point3::new(-2.0, -1.0, 0.0),
         Point3::new(2.0, -1.0, 0.0),
@@ -608,6 +1381,11 @@ fn test_aabb_and_obb() {
 }
 
 pub fn compute_aabb_and_plane(aabb: &Aabb, plane: &Plane) -> bool {
+    let radius = plane.normal().abs().dot(&aabb.2.half_extents());
+    plane.signed_distance(&aabb.2.center()).abs() <= radius
+}
+
+pub fn compute_aabb_and_half_space(aabb: &Aabb, plane: &Plane) -> bool {
     parry3d::query::intersection_test(&aabb.1, &aabb.0, &plane.1, &plane.0).unwrap()
 }
 
@@ -629,6 +1407,12 @@ fn test_aabb_and_plane() {
         Isometry::translation(0.0, -2.0, 0.0),
     );
     assert_eq!(compute_aabb_and_plane(&aabb0, &plane), false);
+
+    let plane = Plane::new(Point3::new(0.0, 10.0, 0.0), Vector3::new(1.0, 1.0, 0.0)).unwrap();
+    assert_eq!(compute_aabb_and_plane(&aabb0, &plane), false);
+    assert_eq!(compute_aabb_and_half_space(&aabb0, &plane), true);
+    let plane = Plane::new(Point3::new(1.9, 0.0, 0.0), Vector3::new(1.0, 1.0, 0.0)).unwrap();
+    assert_eq!(compute_aabb_and_plane(&aabb0, &plane), true);
 }
 
 pub fn compute_aabb_and_triangle(aabb: &Aabb, tri: &Triangle) -> bool {
@@ -680,7 +1464,7 @@ fn test_aabb_and_frustum() {
 
     let points = vec![
         // near
-        Point3::new(2.0f32, 1.0, 0.0),
+        Point3::new(2.0, 1.0, 0.0),
         Point3::new(-2.0, 1.0, 0.0),
         Point3::new(-2.0, -1.0, 0.0),
         Point3::new(2.0, -1.0, 0.0),
@@ -759,6 +1543,13 @@ fn test_obb_and_obb() {
 }
 
 pub fn compute_obb_and_plane(obb: &Obb, plane: &Plane) -> bool {
+    let normal = obb.1.rotation.inverse_transform_vector(&plane.normal());
+    let radius = normal.abs().dot(&obb.0.half_extents);
+    let center = Point3::from(obb.1.translation.vector);
+    plane.signed_distance(&center).abs() <= radius
+}
+
+pub fn compute_obb_and_half_space(obb: &Obb, plane: &Plane) -> bool {
     parry3d::query::intersection_test(&obb.1, &obb.0, &plane.1, &plane.0).unwrap()
 }
 
@@ -784,6 +1575,17 @@ fn test_obb_and_plane() {
         Isometry::translation(0.0, -2.0, 0.0),
     );
     assert_eq!(compute_obb_and_plane(&obb0, &plane), false);
+
+    let obb = Obb::new(
+        Point3::new(0.0, -3.0, 0.0),
+        Vector3::new(0.0, 0.0, std::f64::consts::FRAC_PI_4 as Real),
+        Vector3::new(1.0, 1.0, 1.0),
+    );
+    let plane = Plane::new(Point3::new(0.0, -1.7, 0.0), Vector3::y()).unwrap();
+    assert_eq!(compute_obb_and_plane(&obb, &plane), true);
+    let plane = Plane::new(Point3::new(0.0, -1.5, 0.0), Vector3::y()).unwrap();
+    assert_eq!(compute_obb_and_plane(&obb, &plane), false);
+    assert_eq!(compute_obb_and_half_space(&obb, &plane), true);
 }
 
 pub fn compute_obb_and_triangle(obb: &Obb, tri: &Triangle) -> bool {
@@ -829,7 +1631,49 @@ fn test_obb_and_triangle() {
     assert_eq!(compute_obb_and_triangle(&obb0, &tri), false);
 }
 
+pub fn compute_obb_and_frustum(obb: &Obb, frustum: &Frustum) -> bool {
+    parry3d::query::intersection_test(&obb.1, &obb.0, &frustum.1, &frustum.0).unwrap()
+}
+
+#[cfg(test)]
+use crate::shape::test_frustum;
+
+#[test]
+fn test_obb_and_frustum() {
+    let frustum = test_frustum();
+
+    let obb = Obb::new(
+        Point3::new(0.0, 0.0, -2.0),
+        Vector3::new(0.0, 0.5, 0.0),
+        Vector3::new(1.0, 1.0, 1.0),
+    );
+    assert_eq!(compute_obb_and_frustum(&obb, &frustum), true);
+
+    // Only an edge of the rotated box reaches through the near plane.
+    let obb = Obb::new(
+        Point3::new(0.0, 0.0, 1.3),
+        Vector3::new(0.0, std::f64::consts::FRAC_PI_4 as Real, 0.0),
+        Vector3::new(1.0, 1.0, 1.0),
+    );
+    assert_eq!(compute_obb_and_frustum(&obb, &frustum), true);
+
+    let obb = Obb::new(
+        Point3::new(3.0, 0.0, 1.0),
+        Vector3::new(0.0, std::f64::consts::FRAC_PI_4 as Real, 0.0),
+        Vector3::new(1.0, 1.0, 1.0),
+    );
+    assert_eq!(compute_obb_and_frustum(&obb, &frustum), false);
+}
+
 pub fn compute_plane_and_triangle(plane: &Plane, tri: &Triangle) -> bool {
+    let d = tri
+        .0
+        .vertices()
+        .map(|p| plane.signed_distance(&(tri.1 * p)));
+    d.iter().any(|d| *d <= 0.0) && d.iter().any(|d| *d >= 0.0)
+}
+
+pub fn compute_half_space_and_triangle(plane: &Plane, tri: &Triangle) -> bool {
     parry3d::query::intersection_test(&plane.1, &plane.0, &tri.1, &tri.0).unwrap()
 }
 
@@ -866,6 +1710,44 @@ fn test_plane_and_triangle() {
         Isometry::translation(0.0, 1.0, 0.0),
     );
     assert_eq!(compute_plane_and_triangle(&plane, &tri), false);
+
+    let tri = Triangle(tri.0, Isometry::translation(0.0, -3.0, 0.0));
+    assert_eq!(compute_plane_and_triangle(&plane, &tri), false);
+    assert_eq!(compute_half_space_and_triangle(&plane, &tri), true);
+}
+
+pub fn compute_plane_and_frustum(plane: &Plane, frustum: &Frustum) -> bool {
+    let d: Vec<Real> = frustum
+        .0
+        .points()
+        .iter()
+        .map(|p| plane.signed_distance(&(frustum.1 * p)))
+        .collect();
+    d.iter().any(|d| *d <= 0.0) && d.iter().any(|d| *d >= 0.0)
+}
+
+pub fn compute_half_space_and_frustum(plane: &Plane, frustum: &Frustum) -> bool {
+    parry3d::query::intersection_test(&plane.1, &plane.0, &frustum.1, &frustum.0).unwrap()
+}
+
+#[test]
+fn test_plane_and_frustum() {
+    let frustum = test_frustum();
+
+    let plane = Plane::new(Point3::new(0.0, 0.0, -3.0), Vector3::z()).unwrap();
+    assert_eq!(compute_plane_and_frustum(&plane, &frustum), true);
+    assert_eq!(compute_half_space_and_frustum(&plane, &frustum), true);
+
+    let plane = Plane::new(Point3::new(0.0, 0.0, 1.0), Vector3::z()).unwrap();
+    assert_eq!(compute_plane_and_frustum(&plane, &frustum), false);
+    assert_eq!(compute_half_space_and_frustum(&plane, &frustum), true);
+
+    let plane = Plane::new(Point3::new(0.0, 0.0, 1.0), -Vector3::z()).unwrap();
+    assert_eq!(compute_plane_and_frustum(&plane, &frustum), false);
+    assert_eq!(compute_half_space_and_frustum(&plane, &frustum), false);
+
+    let plane = Plane::new(Point3::new(2.9, 0.0, 0.0), Vector3::new(1.0, 0.0, -0.2)).unwrap();
+    assert_eq!(compute_plane_and_frustum(&plane, &frustum), true);
 }
 
 pub fn compute_triangle_and_triangle(tri0: &Triangle, tri1: &Triangle) -> bool {
@@ -913,3 +1795,59 @@ fn test_triangle_and_triangle() {
     );
     assert_eq!(compute_triangle_and_triangle(&tri0, &tri), false);
 }
+
+pub fn compute_triangle_and_frustum(tri: &Triangle, frustum: &Frustum) -> bool {
+    parry3d::query::intersection_test(&tri.1, &tri.0, &frustum.1, &frustum.0).unwrap()
+}
+
+#[test]
+fn test_triangle_and_frustum() {
+    let frustum = test_frustum();
+    let tri = MTriangle::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+
+    let triangle = Triangle(tri, Isometry::translation(0.0, 0.0, -2.0));
+    assert_eq!(compute_triangle_and_frustum(&triangle, &frustum), true);
+
+    // Crosses the frustum with all its vertices outside.
+    let triangle = Triangle(
+        MTriangle::new(
+            Point3::new(-10.0, 0.0, -2.0),
+            Point3::new(10.0, 0.0, -2.0),
+            Point3::new(0.0, 0.0, 10.0),
+        ),
+        Isometry::identity(),
+    );
+    assert_eq!(compute_triangle_and_frustum(&triangle, &frustum), true);
+
+    let triangle = Triangle(tri, Isometry::translation(0.0, 0.0, -6.0));
+    assert_eq!(compute_triangle_and_frustum(&triangle, &frustum), false);
+}
+
+pub fn compute_frustum_and_frustum(frustum0: &Frustum, frustum1: &Frustum) -> bool {
+    parry3d::query::intersection_test(&frustum0.1, &frustum0.0, &frustum1.1, &frustum1.0).unwrap()
+}
+
+#[test]
+fn test_frustum_and_frustum() {
+    let frustum0 = test_frustum();
+
+    let frustum = Frustum(test_frustum().0, Isometry::translation(0.0, 0.0, -4.0));
+    assert_eq!(compute_frustum_and_frustum(&frustum0, &frustum), true);
+
+    // Light frustum looking down at the camera frustum.
+    let frustum = Frustum(
+        test_frustum().0,
+        Isometry::new(
+            Vector3::new(0.0, 4.0, -2.0),
+            Vector3::new(-std::f64::consts::FRAC_PI_2 as Real, 0.0, 0.0),
+        ),
+    );
+    assert_eq!(compute_frustum_and_frustum(&frustum0, &frustum), true);
+
+    let frustum = Frustum(test_frustum().0, Isometry::translation(0.0, 0.0, -5.5));
+    assert_eq!(compute_frustum_and_frustum(&frustum0, &frustum), false);
+}
@@ -0,0 +1,294 @@
+use crate::debug::debug_triangles;
+use crate::screen::{NdcRect, Projection};
+use crate::shape::{Aabb, Matrix4, Obb, Point3, Real, Sphere};
+use crate::Geomery;
+
+#[allow(unused_imports)]
+use crate::shape::{Ball, Isometry, Vector3};
+
+// Software depth buffer for occlusion culling. Occluders are rasterized at
+// pixel centers keeping the nearest depth, occludees are tested against a
+// pyramid keeping the farthest depth of each 2x2 block.
+pub struct OcclusionBuffer {
+    width: usize,
+    height: usize,
+    projection: Projection,
+    // Level 0 is the depth buffer, depth is 0 at the near plane and 1 at the far plane.
+    levels: Vec<Vec<Real>>,
+}
+
+impl OcclusionBuffer {
+    // Clip space depth in `[0, 1]`, as glam, wgpu and vulkan.
+    pub fn new(width: usize, height: usize, view_proj: impl Into<Matrix4>) -> Self {
+        Self::with_projection(width, height, Projection::new(view_proj))
+    }
+
+    // Clip space depth in `[-1, 1]`, as opengl and nalgebra.
+    pub fn new_gl(width: usize, height: usize, view_proj: impl Into<Matrix4>) -> Self {
+        Self::with_projection(width, height, Projection::new_gl(view_proj))
+    }
+
+    pub fn with_projection(width: usize, height: usize, projection: Projection) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut buffer = Self {
+            width,
+            height,
+            projection,
+            levels: Vec::new(),
+        };
+        buffer.clear();
+        buffer
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn set_view_projection(&mut self, view_proj: impl Into<Matrix4>) {
+        self.projection.view_proj = view_proj.into();
+        self.clear();
+    }
+
+    // Removes every occluder.
+    pub fn clear(&mut self) {
+        self.levels.clear();
+        let (mut w, mut h) = (self.width, self.height);
+        loop {
+            self.levels.push(vec![1.0; w * h]);
+            if w == 1 && h == 1 {
+                break;
+            }
+            (w, h) = (w.div_ceil(2), h.div_ceil(2));
+        }
+    }
+
+    pub fn depth(&self, x: usize, y: usize) -> Real {
+        self.levels[0][y * self.width + x]
+    }
+
+    // Triangles crossing the near plane are skipped, occluders only ever hide less.
+    pub fn add_triangle(&mut self, points: &[Point3; 3]) {
+        let [a, b, c] = points.map(|p| self.to_screen(&p));
+        let (Some(a), Some(b), Some(c)) = (a, b, c) else {
+            return;
+        };
+
+        let area = edge(&a, &b, &c);
+        if area == 0.0 {
+            return;
+        }
+        let x0 = a.x.min(b.x).min(c.x).max(0.0) as usize;
+        let y0 = a.y.min(b.y).min(c.y).max(0.0) as usize;
+        let x1 = (a.x.max(b.x).max(c.x).ceil() as usize).min(self.width);
+        let y1 = (a.y.max(b.y).max(c.y).ceil() as usize).min(self.height);
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let p = Point3::new(x as Real + 0.5, y as Real + 0.5, 0.0);
+                let (w0, w1, w2) = (
+                    edge(&b, &c, &p) / area,
+                    edge(&c, &a, &p) / area,
+                    edge(&a, &b, &p) / area,
+                );
+                if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                    continue;
+                }
+
+                let z = a.z * w0 + b.z * w1 + c.z * w2;
+                let depth = &mut self.levels[0][y * self.width + x];
+                *depth = depth.min(z);
+            }
+        }
+    }
+
+    // Solids are rasterized from their debug tessellation, spheres are inscribed so
+    // they never hide more than they cover. Points, rays and planes are skipped.
+    pub fn add_occluder(&mut self, geomery: &Geomery) {
+        if let Geomery::Plane(_) = geomery {
+            return;
+        }
+        for face in debug_triangles(geomery) {
+            self.add_triangle(&face);
+        }
+    }
+
+    // Must be called after adding occluders and before testing occludees.
+    pub fn build_hiz(&mut self) {
+        for level in 1..self.levels.len() {
+            let (w, h) = self.level_size(level - 1);
+            let (lw, lh) = self.level_size(level);
+            for y in 0..lh {
+                for x in 0..lw {
+                    let src = &self.levels[level - 1];
+                    let (x0, y0) = (x * 2, y * 2);
+                    let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+                    let depth = src[y0 * w + x0]
+                        .max(src[y0 * w + x1])
+                        .max(src[y1 * w + x0])
+                        .max(src[y1 * w + x1]);
+                    self.levels[level][y * lw + x] = depth;
+                }
+            }
+        }
+    }
+
+    fn level_size(&self, level: usize) -> (usize, usize) {
+        let (mut w, mut h) = (self.width, self.height);
+        for _ in 0..level {
+            (w, h) = (w.div_ceil(2), h.div_ceil(2));
+        }
+        (w, h)
+    }
+
+    // Pixel coordinates with the origin at the top left, and depth in `[0, 1]`.
+    // None behind the near plane.
+    fn to_screen(&self, p: &Point3) -> Option<Point3> {
+        let ndc = self.projection.project_point(p)?;
+        Some(Point3::new(
+            (ndc.x + 1.0) * 0.5 * self.width as Real,
+            (1.0 - ndc.y) * 0.5 * self.height as Real,
+            ndc.z,
+        ))
+    }
+
+    // Visible unless every pixel under the screen bounds is nearer than their nearest
+    // depth. Bounds crossing the near plane start at depth 0 and are always visible.
+    fn test_rect(&self, rect: Option<NdcRect>) -> bool {
+        let rect = match rect {
+            Some(rect) => rect,
+            None => return false,
+        };
+        let screen = rect.to_screen(self.width as Real, self.height as Real);
+
+        let x0 = (screen.min.x as usize).min(self.width - 1);
+        let y0 = (screen.min.y as usize).min(self.height - 1);
+        let x1 = (screen.max.x as usize).min(self.width - 1);
+        let y1 = (screen.max.y as usize).min(self.height - 1);
+
+        // Coarsest level where the bounds cover at most 2x2 texels.
+        let mut level = 0;
+        while level + 1 < self.levels.len()
+            && ((x1 >> level) - (x0 >> level) > 1 || (y1 >> level) - (y0 >> level) > 1)
+        {
+            level += 1;
+        }
+
+        let (w, _) = self.level_size(level);
+        let depths = &self.levels[level];
+        ((y0 >> level)..=(y1 >> level))
+            .any(|y| ((x0 >> level)..=(x1 >> level)).any(|x| rect.depth <= depths[y * w + x]))
+    }
+
+    pub fn is_aabb_visible(&self, aabb: &Aabb) -> bool {
+        self.test_rect(self.projection.project_aabb(aabb))
+    }
+
+    pub fn is_obb_visible(&self, obb: &Obb) -> bool {
+        self.test_rect(self.projection.project_obb(obb))
+    }
+
+    pub fn is_sphere_visible(&self, sphere: &Sphere) -> bool {
+        self.test_rect(self.projection.project_sphere(sphere))
+    }
+}
+
+fn edge(a: &Point3, b: &Point3, p: &Point3) -> Real {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+#[cfg(test)]
+fn test_buffer() -> OcclusionBuffer {
+    let view = Isometry::look_at_rh(
+        &Point3::new(0.0, 0.0, 10.0),
+        &Point3::origin(),
+        &Vector3::y(),
+    );
+    let proj = parry3d::na::Perspective3::new(1.0, std::f64::consts::FRAC_PI_2 as Real, 1.0, 100.0);
+    OcclusionBuffer::new_gl(64, 64, proj.to_homogeneous() * view.to_homogeneous())
+}
+
+#[test]
+fn test_rasterize() {
+    let mut buffer = test_buffer();
+    assert_eq!(buffer.depth(32, 32), 1.0);
+
+    // Wall facing the camera at z = 0, 10 units away.
+    buffer.add_occluder(&Geomery::Aabb(Aabb::new(
+        Point3::new(-5.0, -5.0, -0.5),
+        Point3::new(5.0, 5.0, 0.0),
+    )));
+    let center = buffer.depth(32, 32);
+    assert!(center < 1.0);
+    assert_eq!(buffer.depth(0, 0), 1.0);
+    assert_eq!(buffer.depth(63, 32), 1.0);
+
+    // Nearer occluders win, farther ones are hidden.
+    buffer.add_triangle(&[
+        Point3::new(-1.0, -1.0, 5.0),
+        Point3::new(1.0, -1.0, 5.0),
+        Point3::new(0.0, 1.0, 5.0),
+    ]);
+    assert!(buffer.depth(32, 32) < center);
+    let near = buffer.depth(32, 32);
+    buffer.add_triangle(&[
+        Point3::new(-1.0, -1.0, -5.0),
+        Point3::new(1.0, -1.0, -5.0),
+        Point3::new(0.0, 1.0, -5.0),
+    ]);
+    assert_eq!(buffer.depth(32, 32), near);
+
+    // Behind the camera.
+    let mut buffer = test_buffer();
+    buffer.add_triangle(&[
+        Point3::new(-1.0, -1.0, 20.0),
+        Point3::new(1.0, -1.0, 20.0),
+        Point3::new(0.0, 1.0, 20.0),
+    ]);
+    assert_eq!(buffer.depth(32, 32), 1.0);
+}
+
+#[test]
+fn test_occlusion() {
+    let mut buffer = test_buffer();
+    buffer.add_occluder(&Geomery::Aabb(Aabb::new(
+        Point3::new(-5.0, -5.0, -0.5),
+        Point3::new(5.0, 5.0, 0.0),
+    )));
+    buffer.build_hiz();
+
+    let hidden = Aabb::new(Point3::new(-1.0, -1.0, -5.0), Point3::new(1.0, 1.0, -3.0));
+    assert_eq!(buffer.is_aabb_visible(&hidden), false);
+    let peeking = Aabb::new(Point3::new(3.0, -1.0, -5.0), Point3::new(8.0, 1.0, -3.0));
+    assert_eq!(buffer.is_aabb_visible(&peeking), true);
+    let front = Aabb::new(Point3::new(-1.0, -1.0, 2.0), Point3::new(1.0, 1.0, 3.0));
+    assert_eq!(buffer.is_aabb_visible(&front), true);
+    let offscreen = Aabb::new(Point3::new(50.0, -1.0, -5.0), Point3::new(51.0, 1.0, -3.0));
+    assert_eq!(buffer.is_aabb_visible(&offscreen), false);
+    let around_camera = Aabb::new(Point3::new(-1.0, -1.0, 8.0), Point3::new(1.0, 1.0, 12.0));
+    assert_eq!(buffer.is_aabb_visible(&around_camera), true);
+
+    let obb = Obb::new(
+        Point3::new(0.0, 0.0, -4.0),
+        Vector3::new(0.0, 0.0, 0.7),
+        Vector3::new(2.0, 1.0, 1.0),
+    );
+    assert_eq!(buffer.is_obb_visible(&obb), false);
+    let obb = Obb::new(
+        Point3::new(4.5, 0.0, -4.0),
+        Vector3::new(0.0, 0.0, 0.7),
+        Vector3::new(2.0, 1.0, 1.0),
+    );
+    assert_eq!(buffer.is_obb_visible(&obb), true);
+
+    let sphere = Sphere(Ball::new(1.0), Isometry::translation(0.0, 0.0, -4.0));
+    assert_eq!(buffer.is_sphere_visible(&sphere), false);
+    let sphere = Sphere(Ball::new(1.0), Isometry::translation(0.0, 6.5, -4.0));
+    assert_eq!(buffer.is_sphere_visible(&sphere), true);
+
+    buffer.clear();
+    assert_eq!(buffer.is_aabb_visible(&hidden), true);
+}
@@ -0,0 +1,273 @@
+use parry3d::na::{Point2 as Point2D, Vector4 as Vector4D};
+
+use crate::shape::{Aabb, Matrix4, Obb, Point3, Real, Sphere, Vector3};
+
+#[allow(unused_imports)]
+use crate::shape::{ApproxEq, Ball, Isometry};
+
+pub type Point2 = Point2D<Real>;
+type Vector4 = Vector4D<Real>;
+
+// World to normalized device coordinates through a view projection matrix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projection {
+    pub view_proj: Matrix4,
+    near_z: Real,
+}
+
+// Screen bounds in normalized device coordinates, y up, clipped to `[-1, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NdcRect {
+    pub min: Point2,
+    pub max: Point2,
+    // Nearest depth, 0 at the near plane and 1 at the far plane.
+    pub depth: Real,
+}
+
+// Screen bounds in pixels, with the origin at the top left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenRect {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Projection {
+    // Clip space depth in `[0, 1]`, as glam, wgpu and vulkan.
+    pub fn new(view_proj: impl Into<Matrix4>) -> Self {
+        Self {
+            view_proj: view_proj.into(),
+            near_z: 0.0,
+        }
+    }
+
+    // Clip space depth in `[-1, 1]`, as opengl and nalgebra.
+    pub fn new_gl(view_proj: impl Into<Matrix4>) -> Self {
+        Self {
+            view_proj: view_proj.into(),
+            near_z: -1.0,
+        }
+    }
+
+    // Normalized device coordinates with the depth in `[0, 1]`, None behind the near plane.
+    pub fn project_point(&self, point: &Point3) -> Option<Point3> {
+        self.clip_to_ndc(&(self.view_proj * point.to_homogeneous()))
+    }
+
+    fn clip_to_ndc(&self, clip: &Vector4) -> Option<Point3> {
+        if clip.w <= 0.0 || self.near_distance(clip) < 0.0 {
+            return None;
+        }
+
+        let ndc = clip.xyz() / clip.w;
+        Some(Point3::new(
+            ndc.x,
+            ndc.y,
+            (ndc.z - self.near_z) / (1.0 - self.near_z),
+        ))
+    }
+
+    // Positive in front of the near plane.
+    fn near_distance(&self, clip: &Vector4) -> Real {
+        clip.z - self.near_z * clip.w
+    }
+
+    // Bounds of the convex hull of `points`, the part behind the near plane is cut
+    // off first. None when nothing is left on screen.
+    pub fn project_points(&self, points: &[Point3]) -> Option<NdcRect> {
+        let clip: Vec<Vector4> = points
+            .iter()
+            .map(|p| self.view_proj * p.to_homogeneous())
+            .collect();
+
+        // Segments between every pair of points cover the edges of the hull, where
+        // they cross the near plane gives the corners of the cut.
+        let mut front: Vec<Point3> = clip.iter().filter_map(|c| self.clip_to_ndc(c)).collect();
+        for (i, a) in clip.iter().enumerate() {
+            for b in &clip[i + 1..] {
+                let (da, db) = (self.near_distance(a), self.near_distance(b));
+                if (da < 0.0) != (db < 0.0) {
+                    let mut cut = a + (b - a) * (da / (da - db));
+                    // Snapped onto the plane against rounding.
+                    cut.z = self.near_z * cut.w;
+                    front.extend(self.clip_to_ndc(&cut));
+                }
+            }
+        }
+
+        let first = front.first()?;
+        let (min, max) = front
+            .iter()
+            .fold((*first, *first), |(min, max), p| (min.inf(p), max.sup(p)));
+        if max.x < -1.0 || max.y < -1.0 || min.x > 1.0 || min.y > 1.0 || min.z > 1.0 {
+            return None;
+        }
+
+        let clamp = |v: Real| v.clamp(-1.0, 1.0);
+        Some(NdcRect {
+            min: Point2::new(clamp(min.x), clamp(min.y)),
+            max: Point2::new(clamp(max.x), clamp(max.y)),
+            depth: min.z.max(0.0),
+        })
+    }
+
+    pub fn project_aabb(&self, aabb: &Aabb) -> Option<NdcRect> {
+        self.project_points(&aabb.corners())
+    }
+
+    pub fn project_obb(&self, obb: &Obb) -> Option<NdcRect> {
+        self.project_points(&obb.corners())
+    }
+
+    // Through the corners of its bounding box, so a bit larger than the sphere.
+    pub fn project_sphere(&self, sphere: &Sphere) -> Option<NdcRect> {
+        let center = Point3::from(sphere.1.translation.vector);
+        let r = Vector3::repeat(sphere.0.radius);
+        self.project_aabb(&Aabb::new(center - r, center + r))
+    }
+}
+
+impl NdcRect {
+    // Part of the screen covered, in `[0, 1]`.
+    pub fn coverage(&self) -> Real {
+        let size = self.max - self.min;
+        size.x * size.y * 0.25
+    }
+
+    pub fn to_screen(&self, width: Real, height: Real) -> ScreenRect {
+        let pixel =
+            |x: Real, y: Real| Point2::new((x + 1.0) * 0.5 * width, (1.0 - y) * 0.5 * height);
+        ScreenRect {
+            min: pixel(self.min.x, self.max.y),
+            max: pixel(self.max.x, self.min.y),
+        }
+    }
+}
+
+impl ScreenRect {
+    pub fn width(&self) -> Real {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> Real {
+        self.max.y - self.min.y
+    }
+
+    // Pixels covered.
+    pub fn area(&self) -> Real {
+        self.width() * self.height()
+    }
+
+    // Largest side in pixels, the usual metric for picking a level of detail.
+    pub fn size(&self) -> Real {
+        self.width().max(self.height())
+    }
+}
+
+#[cfg(test)]
+fn point2_approx_eq(a: &Point2, b: &Point2, epsilon: Real) -> bool {
+    (a - b).amax() <= epsilon
+}
+
+#[cfg(test)]
+fn test_projection() -> Projection {
+    let view = Isometry::look_at_rh(
+        &Point3::new(0.0, 0.0, 10.0),
+        &Point3::origin(),
+        &Vector3::y(),
+    );
+    let proj = parry3d::na::Perspective3::new(1.0, std::f64::consts::FRAC_PI_2 as Real, 1.0, 100.0);
+    Projection::new_gl(proj.to_homogeneous() * view.to_homogeneous())
+}
+
+#[test]
+fn test_project_point() {
+    let projection = test_projection();
+
+    let p = projection
+        .project_point(&Point3::new(5.0, 2.5, 0.0))
+        .unwrap();
+    assert!(p.approx_eq(&Point3::new(0.5, 0.25, p.z), 1.0e-5));
+    assert!(p.z > 0.0 && p.z < 1.0);
+
+    let near = projection
+        .project_point(&Point3::new(0.0, 0.0, 8.999))
+        .unwrap();
+    assert!(near.z.approx_eq(&0.0, 1.0e-2));
+    assert!(projection
+        .project_point(&Point3::new(0.0, 0.0, 9.5))
+        .is_none());
+    assert!(projection
+        .project_point(&Point3::new(0.0, 0.0, 20.0))
+        .is_none());
+}
+
+#[test]
+fn test_project_bounds() {
+    let projection = test_projection();
+
+    let aabb = Aabb::new(Point3::new(-5.0, -5.0, -10.0), Point3::new(5.0, 5.0, 0.0));
+    let rect = projection.project_aabb(&aabb).unwrap();
+    assert!(point2_approx_eq(
+        &rect.min,
+        &Point2::new(-0.5, -0.5),
+        1.0e-5
+    ));
+    assert!(point2_approx_eq(&rect.max, &Point2::new(0.5, 0.5), 1.0e-5));
+    assert!(rect.coverage().approx_eq(&0.25, 1.0e-5));
+
+    let screen = rect.to_screen(200.0, 100.0);
+    assert!(point2_approx_eq(
+        &screen.min,
+        &Point2::new(50.0, 25.0),
+        1.0e-3
+    ));
+    assert!(point2_approx_eq(
+        &screen.max,
+        &Point2::new(150.0, 75.0),
+        1.0e-3
+    ));
+    assert!(screen.area().approx_eq(&5000.0, 1.0e-1));
+    assert!(screen.size().approx_eq(&100.0, 1.0e-3));
+
+    // Straddling the near plane, the corners behind the camera would flip the rect.
+    let aabb = Aabb::new(Point3::new(0.5, -0.25, 5.0), Point3::new(1.0, 0.25, 15.0));
+    let rect = projection.project_aabb(&aabb).unwrap();
+    assert_eq!(rect.depth, 0.0);
+    assert!(point2_approx_eq(
+        &rect.min,
+        &Point2::new(0.1, -0.25),
+        1.0e-5
+    ));
+    assert!(point2_approx_eq(&rect.max, &Point2::new(1.0, 0.25), 1.0e-5));
+
+    let behind = Aabb::new(Point3::new(-1.0, -1.0, 11.0), Point3::new(1.0, 1.0, 12.0));
+    assert!(projection.project_aabb(&behind).is_none());
+    let offscreen = Aabb::new(Point3::new(30.0, -1.0, -1.0), Point3::new(31.0, 1.0, 1.0));
+    assert!(projection.project_aabb(&offscreen).is_none());
+
+    let obb = Obb::new(
+        Point3::origin(),
+        Vector3::new(0.0, 0.0, std::f64::consts::FRAC_PI_4 as Real),
+        Vector3::new(1.0, 1.0, 0.0),
+    );
+    let rect = projection.project_obb(&obb).unwrap();
+    assert!(point2_approx_eq(
+        &rect.max,
+        &(Point2::new(0.1, 0.1) * Real::sqrt(2.0)),
+        1.0e-5
+    ));
+
+    let sphere = Sphere(Ball::new(1.0), Isometry::identity());
+    let rect = projection.project_sphere(&sphere).unwrap();
+    assert!(point2_approx_eq(
+        &rect.max,
+        &Point2::new(1.0 / 9.0, 1.0 / 9.0),
+        1.0e-5
+    ));
+    assert!(projection
+        .project_sphere(&Sphere(
+            Ball::new(1.0),
+            Isometry::translation(0.0, 0.0, 9.5)
+        ))
+        .is_some_and(|rect| rect.coverage() > 0.99));
+}
@@ -0,0 +1,413 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::shape::{
+    Aabb, Ball, ConvexHull, Cuboid, Frustum, HalfSpace, Isometry, MTriangle, Obb, Plane, Point3,
+    Ray, Real, Sphere, TriMesh, Triangle, Vector3,
+};
+use crate::Geomery;
+
+use parry3d::na::{Quaternion, Translation3, Unit, UnitQuaternion};
+
+type Array3 = [Real; 3];
+
+fn to_array(v: &Vector3) -> Array3 {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(a: &Array3) -> Vector3 {
+    Vector3::new(a[0], a[1], a[2])
+}
+
+fn to_point(a: &Array3) -> Point3 {
+    Point3::new(a[0], a[1], a[2])
+}
+
+// Rotation is stored as quaternion `[i, j, k, w]`.
+#[derive(Serialize, Deserialize)]
+struct IsometryRepr {
+    translation: Array3,
+    rotation: [Real; 4],
+}
+
+impl From<&Isometry> for IsometryRepr {
+    fn from(iso: &Isometry) -> Self {
+        let q = iso.rotation.quaternion();
+        Self {
+            translation: to_array(&iso.translation.vector),
+            rotation: [q.i, q.j, q.k, q.w],
+        }
+    }
+}
+
+impl From<&IsometryRepr> for Isometry {
+    fn from(repr: &IsometryRepr) -> Self {
+        let [i, j, k, w] = repr.rotation;
+        let q = Quaternion::new(w, i, j, k);
+        // Keep already normalized rotations bit exact so round trips are stable.
+        let rotation = if (q.norm_squared() - 1.0).abs() <= Real::EPSILON * 4.0 {
+            UnitQuaternion::new_unchecked(q)
+        } else {
+            UnitQuaternion::from_quaternion(q)
+        };
+
+        Isometry::from_parts(Translation3::from(to_vector(&repr.translation)), rotation)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RayRepr {
+    origin: Array3,
+    dir: Array3,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BallRepr {
+    radius: Real,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SphereRepr {
+    radius: Real,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PlaneRepr {
+    normal: Array3,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TriangleRepr {
+    a: Array3,
+    b: Array3,
+    c: Array3,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AabbRepr {
+    mins: Array3,
+    maxs: Array3,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ObbRepr {
+    half_extents: Array3,
+    isometry: IsometryRepr,
+}
+
+// Only the hull points are stored, the topology is rebuilt on load.
+#[derive(Serialize, Deserialize)]
+struct FrustumRepr {
+    points: Vec<Array3>,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConvexHullRepr {
+    points: Vec<Array3>,
+    isometry: IsometryRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TriMeshRepr {
+    vertices: Vec<Array3>,
+    indices: Vec<[u32; 3]>,
+    isometry: IsometryRepr,
+}
+
+macro_rules! impl_serde {
+    ($ty: ty, $repr: ident, |$s: ident| $to: expr, |$r: ident| $from: expr) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let $s = self;
+                let repr: $repr = $to;
+                repr.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let $r = $repr::deserialize(deserializer)?;
+                $from
+            }
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use impl_serde;
+
+impl_serde!(
+    Sphere,
+    SphereRepr,
+    |s| SphereRepr {
+        radius: s.0.radius,
+        isometry: (&s.1).into(),
+    },
+    |r| Ok(Sphere(Ball::new(r.radius), (&r.isometry).into()))
+);
+
+impl_serde!(
+    Plane,
+    PlaneRepr,
+    |s| PlaneRepr {
+        normal: to_array(&s.0.normal),
+        isometry: (&s.1).into(),
+    },
+    |r| {
+        let normal = Unit::try_new(to_vector(&r.normal), Real::EPSILON)
+            .ok_or_else(|| D::Error::custom("invalid plane normal"))?;
+        Ok(Plane(HalfSpace::new(normal), (&r.isometry).into()))
+    }
+);
+
+impl_serde!(
+    Triangle,
+    TriangleRepr,
+    |s| TriangleRepr {
+        a: to_array(&s.0.a.coords),
+        b: to_array(&s.0.b.coords),
+        c: to_array(&s.0.c.coords),
+        isometry: (&s.1).into(),
+    },
+    |r| Ok(Triangle(
+        MTriangle::new(to_point(&r.a), to_point(&r.b), to_point(&r.c)),
+        (&r.isometry).into()
+    ))
+);
+
+impl_serde!(
+    Aabb,
+    AabbRepr,
+    |s| AabbRepr {
+        mins: to_array(&s.2.mins.coords),
+        maxs: to_array(&s.2.maxs.coords),
+    },
+    |r| Ok(Aabb::new(to_point(&r.mins), to_point(&r.maxs)))
+);
+
+impl_serde!(
+    Obb,
+    ObbRepr,
+    |s| ObbRepr {
+        half_extents: to_array(&s.0.half_extents),
+        isometry: (&s.1).into(),
+    },
+    |r| Ok(Obb(
+        Cuboid::new(to_vector(&r.half_extents)),
+        (&r.isometry).into()
+    ))
+);
+
+impl_serde!(
+    Frustum,
+    FrustumRepr,
+    |s| FrustumRepr {
+        points: s.0.points().iter().map(|p| to_array(&p.coords)).collect(),
+        isometry: (&s.1).into(),
+    },
+    |r| {
+        let points: Vec<Point3> = r.points.iter().map(to_point).collect();
+        let frustum = Frustum::from_points(&points)
+            .ok_or_else(|| D::Error::custom("invalid convex frustum points"))?;
+        Ok(Frustum(frustum.0, (&r.isometry).into()))
+    }
+);
+
+impl_serde!(
+    ConvexHull,
+    ConvexHullRepr,
+    |s| ConvexHullRepr {
+        points: s.0.points().iter().map(|p| to_array(&p.coords)).collect(),
+        isometry: (&s.1).into(),
+    },
+    |r| {
+        let points: Vec<Point3> = r.points.iter().map(to_point).collect();
+        let hull = ConvexHull::new(&points)
+            .ok_or_else(|| D::Error::custom("invalid convex hull points"))?;
+        Ok(ConvexHull(hull.0, (&r.isometry).into()))
+    }
+);
+
+impl_serde!(
+    TriMesh,
+    TriMeshRepr,
+    |s| TriMeshRepr {
+        vertices: s.0.vertices().iter().map(|p| to_array(&p.coords)).collect(),
+        indices: s.0.indices().to_vec(),
+        isometry: (&s.1).into(),
+    },
+    |r| {
+        let vertices = r.vertices.iter().map(to_point).collect();
+        let mesh = TriMesh::new(vertices, r.indices)
+            .ok_or_else(|| D::Error::custom("invalid triangle mesh indices"))?;
+        Ok(TriMesh(mesh.0, (&r.isometry).into()))
+    }
+);
+
+#[derive(Serialize)]
+#[serde(rename = "Geomery")]
+enum GeomeryRef<'a> {
+    Point(Array3),
+    Ray(RayRepr),
+    Plane(&'a Plane),
+    Sphere(&'a Sphere),
+    Aabb(&'a Aabb),
+    Ball(BallRepr),
+    Triangle(&'a Triangle),
+    Frustum(&'a Frustum),
+    Obb(&'a Obb),
+    TriMesh(&'a TriMesh),
+    ConvexHull(&'a ConvexHull),
+    Compound(&'a [Geomery]),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Geomery")]
+enum GeomeryOwned {
+    Point(Array3),
+    Ray(RayRepr),
+    Plane(Plane),
+    Sphere(Sphere),
+    Aabb(Aabb),
+    Ball(BallRepr),
+    Triangle(Triangle),
+    Frustum(Frustum),
+    Obb(Obb),
+    TriMesh(TriMesh),
+    ConvexHull(ConvexHull),
+    Compound(Vec<Geomery>),
+}
+
+impl Serialize for Geomery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            Geomery::Point(p) => GeomeryRef::Point(to_array(&p.coords)),
+            Geomery::Ray(r) => GeomeryRef::Ray(RayRepr {
+                origin: to_array(&r.origin.coords),
+                dir: to_array(&r.dir),
+            }),
+            Geomery::Plane(p) => GeomeryRef::Plane(p),
+            Geomery::Sphere(s) => GeomeryRef::Sphere(s),
+            Geomery::Aabb(ab) => GeomeryRef::Aabb(ab),
+            Geomery::Ball(b) => GeomeryRef::Ball(BallRepr { radius: b.radius }),
+            Geomery::Triangle(t) => GeomeryRef::Triangle(t),
+            Geomery::Frustum(f) => GeomeryRef::Frustum(f),
+            Geomery::Obb(obb) => GeomeryRef::Obb(obb),
+            Geomery::TriMesh(t) => GeomeryRef::TriMesh(t),
+            Geomery::ConvexHull(c) => GeomeryRef::ConvexHull(c),
+            Geomery::Compound(parts) => GeomeryRef::Compound(parts),
+            Geomery::Custom(_) => {
+                return Err(serde::ser::Error::custom(
+                    "custom shapes can not be serialized",
+                ))
+            }
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Geomery {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match GeomeryOwned::deserialize(deserializer)? {
+            GeomeryOwned::Point(p) => Geomery::Point(to_point(&p)),
+            GeomeryOwned::Ray(r) => Geomery::Ray(Ray::new(to_point(&r.origin), to_vector(&r.dir))),
+            GeomeryOwned::Plane(p) => Geomery::Plane(p),
+            GeomeryOwned::Sphere(s) => Geomery::Sphere(s),
+            GeomeryOwned::Aabb(ab) => Geomery::Aabb(ab),
+            GeomeryOwned::Ball(b) => Geomery::Ball(Ball::new(b.radius)),
+            GeomeryOwned::Triangle(t) => Geomery::Triangle(t),
+            GeomeryOwned::Frustum(f) => Geomery::Frustum(f),
+            GeomeryOwned::Obb(obb) => Geomery::Obb(obb),
+            GeomeryOwned::TriMesh(t) => Geomery::TriMesh(t),
+            GeomeryOwned::ConvexHull(c) => Geomery::ConvexHull(c),
+            GeomeryOwned::Compound(parts) => Geomery::Compound(parts),
+        })
+    }
+}
+
+#[cfg(test)]
+use crate::test_geomeries;
+
+#[test]
+fn test_serde_json_round_trip() {
+    for geomery in test_geomeries() {
+        let json = serde_json::to_string(&geomery).unwrap();
+        let back: Geomery = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json, "{:?}", geomery);
+    }
+}
+
+#[test]
+fn test_serde_bincode_round_trip() {
+    for geomery in test_geomeries() {
+        let bytes = bincode::serialize(&geomery).unwrap();
+        let back: Geomery = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(bincode::serialize(&back).unwrap(), bytes, "{:?}", geomery);
+    }
+}
+
+#[test]
+fn test_serde_json_format() {
+    let sphere = Geomery::Sphere(Sphere(Ball::new(1.5), Isometry::translation(1.0, 2.0, 3.0)));
+    assert_eq!(
+        serde_json::to_string(&sphere).unwrap(),
+        r#"{"Sphere":{"radius":1.5,"isometry":{"translation":[1.0,2.0,3.0],"rotation":[0.0,0.0,0.0,1.0]}}}"#
+    );
+
+    let aabb: Aabb =
+        serde_json::from_str(r#"{"mins":[0.0,0.0,0.0],"maxs":[2.0,4.0,6.0]}"#).unwrap();
+    assert_eq!(aabb.0.half_extents, Vector3::new(1.0, 2.0, 3.0));
+    assert_eq!(aabb.1.translation.vector, Vector3::new(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn test_serde_frustum() {
+    let frustum = match test_geomeries().remove(7) {
+        Geomery::Frustum(f) => f,
+        _ => unreachable!(),
+    };
+
+    let json = serde_json::to_string(&frustum).unwrap();
+    let back: Frustum = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.0.points().len(), frustum.0.points().len());
+    assert_eq!(back.0.faces().len(), frustum.0.faces().len());
+    assert_eq!(back.1, frustum.1);
+
+    let sphere = Sphere(Ball::new(1.0), Isometry::identity());
+    assert_eq!(crate::compute_sphere_and_frustum(&sphere, &back), true);
+
+    let invalid = r#"{"points":[[0.0,0.0,0.0]],"isometry":{"translation":[0.0,0.0,0.0],"rotation":[0.0,0.0,0.0,1.0]}}"#;
+    assert!(serde_json::from_str::<Frustum>(invalid).is_err());
+}
+
+#[test]
+fn test_serde_degenerate_hulls() {
+    let isometry = r#""isometry":{"translation":[0.0,0.0,0.0],"rotation":[0.0,0.0,0.0,1.0]}"#;
+    let repeated = format!(
+        r#"{{"points":[{}],{}}}"#,
+        ["[1.0,2.0,3.0]"; 4].join(","),
+        isometry
+    );
+    let flat = format!(
+        r#"{{"points":[[0.0,0.0,0.0],[1.0,0.0,0.0],[1.0,1.0,0.0],[0.0,1.0,0.0]],{}}}"#,
+        isometry
+    );
+
+    for json in [&repeated, &flat] {
+        assert!(serde_json::from_str::<Frustum>(json).is_err(), "{}", json);
+        assert!(
+            serde_json::from_str::<ConvexHull>(json).is_err(),
+            "{}",
+            json
+        );
+        let geomery = format!(r#"{{"ConvexHull":{}}}"#, json);
+        assert!(
+            serde_json::from_str::<Geomery>(&geomery).is_err(),
+            "{}",
+            json
+        );
+    }
+}
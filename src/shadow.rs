@@ -0,0 +1,284 @@
+use crate::shape::{Aabb, Ball, Frustum, Isometry, Obb, Point3, Real, Sphere, Vector3};
+
+#[allow(unused_imports)]
+use crate::{compute_sphere_and_frustum, shape::ApproxEq};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitScheme {
+    Uniform,
+    Logarithmic,
+    // Blend of both, 0 is uniform and 1 logarithmic.
+    Practical(Real),
+}
+
+// View depths of the `count + 1` split planes from `near` to `far`.
+// Logarithmic and practical splits need `near > 0`.
+pub fn split_depths(near: Real, far: Real, count: usize, scheme: SplitScheme) -> Option<Vec<Real>> {
+    let lambda = match scheme {
+        SplitScheme::Uniform => 0.0,
+        SplitScheme::Logarithmic => 1.0,
+        SplitScheme::Practical(lambda) => lambda.clamp(0.0, 1.0),
+    };
+    if count == 0 || near < 0.0 || near >= far || (lambda > 0.0 && near == 0.0) {
+        return None;
+    }
+
+    let depths = (0..=count)
+        .map(|i| {
+            if i == count {
+                return far;
+            }
+
+            let t = i as Real / count as Real;
+            let uniform = near + (far - near) * t;
+            if lambda == 0.0 {
+                return uniform;
+            }
+            let log = near * (far / near).powf(t);
+            uniform + (log - uniform) * lambda
+        })
+        .collect();
+    Some(depths)
+}
+
+// One depth slice of the camera frustum.
+#[derive(Debug, Clone)]
+pub struct Cascade {
+    pub near: Real,
+    pub far: Real,
+    // Near corners then far corners, in the order given to `cascades`.
+    pub corners: [Point3; 8],
+    pub frustum: Frustum,
+}
+
+// Splits the camera frustum given by its corners, `near` and `far` are the view
+// depths of the near and far corners.
+pub fn cascades<P: Into<Point3> + Copy>(
+    near_corners: [P; 4],
+    far_corners: [P; 4],
+    near: Real,
+    far: Real,
+    count: usize,
+    scheme: SplitScheme,
+) -> Option<Vec<Cascade>> {
+    let depths = split_depths(near, far, count, scheme)?;
+    let (near_corners, far_corners) = (
+        near_corners.map(|p| p.into()),
+        far_corners.map(|p| p.into()),
+    );
+    // View depth is linear along the edges from the near to the far corners.
+    let slice = |depth: Real| {
+        let t = (depth - near) / (far - near);
+        [0, 1, 2, 3].map(|i| near_corners[i] + (far_corners[i] - near_corners[i]) * t)
+    };
+
+    depths
+        .windows(2)
+        .map(|w| {
+            let (n, f) = (slice(w[0]), slice(w[1]));
+            Some(Cascade {
+                near: w[0],
+                far: w[1],
+                corners: [n[0], n[1], n[2], n[3], f[0], f[1], f[2], f[3]],
+                frustum: Frustum::from_corners(n, f)?,
+            })
+        })
+        .collect()
+}
+
+// World to light space of a directional light shining along `dir`, the light looks down -z.
+pub fn light_view(dir: &Vector3) -> Option<Isometry> {
+    let dir = dir.try_normalize(Real::EPSILON)?;
+    let up = if dir.y.abs() < 0.9 {
+        Vector3::y()
+    } else {
+        Vector3::x()
+    };
+    Some(Isometry::look_at_rh(
+        &Point3::origin(),
+        &Point3::from(dir),
+        &up,
+    ))
+}
+
+impl Cascade {
+    // Box around the corners in light space, `light` maps world to light space.
+    pub fn light_aabb(&self, light: &Isometry) -> Option<Aabb> {
+        Aabb::from_points(&self.corners.map(|p| light * p))
+    }
+
+    // Its size does not change when the camera turns, so shadow texels stay put.
+    pub fn bounding_sphere(&self) -> Sphere {
+        let center = self
+            .corners
+            .iter()
+            .fold(Vector3::zeros(), |acc, p| acc + p.coords)
+            / 8.0;
+        let radius = self
+            .corners
+            .iter()
+            .map(|p| (p.coords - center).norm())
+            .fold(0.0, Real::max);
+        Sphere(
+            Ball::new(radius),
+            Isometry::translation(center.x, center.y, center.z),
+        )
+    }
+
+    // World space orthographic volume of the light around `light_aabb`, extended by
+    // `caster_distance` towards the light so casters in front of the slice are kept.
+    // None when the volume is flat.
+    pub fn light_volume(&self, light: &Isometry, caster_distance: Real) -> Option<Frustum> {
+        let aabb = self.light_aabb(light)?;
+        light_box(light, aabb.mins(), aabb.maxs(), caster_distance)
+    }
+
+    // As `light_volume` around `bounding_sphere`.
+    pub fn sphere_light_volume(&self, light: &Isometry, caster_distance: Real) -> Option<Frustum> {
+        let sphere = self.bounding_sphere();
+        let center = light * Point3::from(sphere.1.translation.vector);
+        let r = Vector3::repeat(sphere.0.radius);
+        light_box(light, center - r, center + r, caster_distance)
+    }
+}
+
+fn light_box(
+    light: &Isometry,
+    mins: Point3,
+    maxs: Point3,
+    caster_distance: Real,
+) -> Option<Frustum> {
+    let aabb = Aabb::new(mins, maxs + Vector3::z() * caster_distance);
+    let world = light.inverse();
+    let obb = Obb::from_rotation(world * aabb.center(), world.rotation, aabb.half_extents());
+    Frustum::from_points(&obb.corners())
+}
+
+#[cfg(test)]
+fn camera_cascades(scheme: SplitScheme) -> Vec<Cascade> {
+    cascades(
+        [
+            Point3::new(-1.0, -1.0, -1.0),
+            Point3::new(1.0, -1.0, -1.0),
+            Point3::new(1.0, 1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+        ],
+        [
+            Point3::new(-100.0, -100.0, -100.0),
+            Point3::new(100.0, -100.0, -100.0),
+            Point3::new(100.0, 100.0, -100.0),
+            Point3::new(-100.0, 100.0, -100.0),
+        ],
+        1.0,
+        100.0,
+        4,
+        scheme,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_split_depths() {
+    let uniform = split_depths(0.0, 100.0, 4, SplitScheme::Uniform).unwrap();
+    assert_eq!(uniform, vec![0.0, 25.0, 50.0, 75.0, 100.0]);
+
+    let log = split_depths(1.0, 100.0, 2, SplitScheme::Logarithmic).unwrap();
+    assert_eq!(log[0], 1.0);
+    assert!(log[1].approx_eq(&10.0, 1.0e-4));
+    assert_eq!(log[2], 100.0);
+
+    let practical = split_depths(1.0, 100.0, 2, SplitScheme::Practical(0.5)).unwrap();
+    assert!(practical[1].approx_eq(&30.25, 1.0e-4));
+
+    assert!(split_depths(0.0, 100.0, 4, SplitScheme::Logarithmic).is_none());
+    assert!(split_depths(1.0, 100.0, 0, SplitScheme::Uniform).is_none());
+    assert!(split_depths(100.0, 1.0, 4, SplitScheme::Uniform).is_none());
+}
+
+#[test]
+fn test_cascades() {
+    let cascades = camera_cascades(SplitScheme::Practical(0.75));
+    assert_eq!(cascades.len(), 4);
+    assert_eq!(cascades[0].near, 1.0);
+    assert_eq!(cascades[3].far, 100.0);
+
+    for pair in cascades.windows(2) {
+        assert_eq!(pair[0].far, pair[1].near);
+        for i in 0..4 {
+            assert!(pair[0].corners[i + 4].approx_eq(&pair[1].corners[i], 1.0e-3));
+        }
+    }
+    for cascade in &cascades {
+        for p in cascade.corners {
+            assert!(
+                (-p.z).approx_eq(&cascade.near, 1.0e-3) || (-p.z).approx_eq(&cascade.far, 1.0e-3)
+            );
+        }
+
+        let middle = Point3::new(0.0, 0.0, -(cascade.near + cascade.far) * 0.5);
+        let sphere = Sphere(
+            Ball::new(0.01),
+            Isometry::translation(middle.x, middle.y, middle.z),
+        );
+        assert_eq!(compute_sphere_and_frustum(&sphere, &cascade.frustum), true);
+    }
+}
+
+#[test]
+fn test_light_bounds() {
+    let cascade = &camera_cascades(SplitScheme::Uniform)[1];
+    let light = light_view(&Vector3::new(0.0, -1.0, -1.0)).unwrap();
+    assert!((light * Point3::new(0.0, -1.0, -1.0))
+        .approx_eq(&Point3::new(0.0, 0.0, -Real::sqrt(2.0)), 1.0e-5));
+
+    let aabb = cascade.light_aabb(&light).unwrap();
+    let sphere = cascade.bounding_sphere();
+    let center = Point3::from(sphere.1.translation.vector);
+    for p in cascade.corners {
+        let q = light * p;
+        assert!(q.coords.inf(&aabb.mins().coords) == aabb.mins().coords);
+        assert!(q.coords.sup(&aabb.maxs().coords) == aabb.maxs().coords);
+        assert!((p - center).norm() <= sphere.0.radius + 1.0e-3);
+    }
+
+    // A caster above the slice, between it and the light, is only kept when extruded.
+    let caster = Sphere(Ball::new(1.0), Isometry::translation(0.0, 60.0, 22.0));
+    assert_eq!(
+        compute_sphere_and_frustum(&caster, &cascade.light_volume(&light, 0.0).unwrap()),
+        false
+    );
+    assert_eq!(
+        compute_sphere_and_frustum(&caster, &cascade.light_volume(&light, 200.0).unwrap()),
+        true
+    );
+    assert_eq!(
+        compute_sphere_and_frustum(
+            &caster,
+            &cascade.sphere_light_volume(&light, 200.0).unwrap()
+        ),
+        true
+    );
+
+    let behind = Sphere(Ball::new(1.0), Isometry::translation(0.0, -60.0, -98.0));
+    assert_eq!(
+        compute_sphere_and_frustum(&behind, &cascade.light_volume(&light, 200.0).unwrap()),
+        false
+    );
+
+    // Degenerate slices have no volume.
+    let mut flat = cascade.clone();
+    flat.corners = [Point3::new(0.0, 0.0, -10.0); 8];
+    assert!(flat.light_volume(&light, 0.0).is_none());
+    assert!(flat.sphere_light_volume(&light, 0.0).is_none());
+    // Flat along the light, only the caster extrusion gives it a depth.
+    flat.corners = cascade.corners.map(|p| {
+        let q = light * p;
+        light.inverse() * Point3::new(q.x, q.y, 0.0)
+    });
+    assert!(flat.light_volume(&light, 0.0).is_none());
+    assert!(flat.light_volume(&light, 10.0).is_some());
+    assert!(cascade.light_volume(&light, 0.0).is_some());
+
+    assert!(light_view(&Vector3::zeros()).is_none());
+    assert!(light_view(&Vector3::new(0.0, -1.0, 0.0)).is_some());
+}
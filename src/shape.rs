@@ -1,32 +1,361 @@
+use std::sync::Arc;
+
 use parry3d::{
-    bounding_volume::aabb::Aabb as AABB,
-    math::{Isometry as Isometry3D, Point, Vector},
-    query::Ray as Ray3D,
+    bounding_volume::{aabb::Aabb as AABB, BoundingSphere, BoundingVolume},
+    mass_properties::MassProperties,
+    math::{Isometry as Isometry3D, Point, Rotation as Rotation3D, Vector},
+    na::{Matrix4 as Matrix4D, RealField, Unit},
+    query::{
+        details::{
+            local_point_projection_on_support_map,
+            local_ray_intersection_with_support_map_with_params,
+        },
+        gjk::VoronoiSimplex,
+        Contact as Contact3D, PointProjection as PointProjection3D, PointQuery, Ray as Ray3D,
+        RayCast, RayIntersection,
+    },
     shape::{
-        Ball as Ball3D, ConvexPolyhedron as ConvexPolyhedron3D, Cuboid as Cuboid3D,
-        HalfSpace as HalfSpace3D, Triangle as Triangle3D,
+        Ball as Ball3D, ConvexPolyhedron as ConvexPolyhedron3D, Cuboid as Cuboid3D, FeatureId,
+        HalfSpace as HalfSpace3D, Shape, ShapeType, SupportMap, TriMesh as TriMesh3D,
+        Triangle as Triangle3D, TypedShape,
     },
 };
 
-pub type Point3 = Point<f32>;
-pub type Vector3 = Vector<f32>;
-pub type Isometry = Isometry3D<f32>;
+pub type Real = parry3d::math::Real;
+pub type Point3 = Point<Real>;
+pub type Vector3 = Vector<Real>;
+pub type Isometry = Isometry3D<Real>;
+pub type Rotation = Rotation3D<Real>;
+pub type Matrix4 = Matrix4D<Real>;
 pub type ConvexPolyhedron = ConvexPolyhedron3D;
 pub type HalfSpace = HalfSpace3D;
 pub type Ball = Ball3D;
 pub type Cuboid = Cuboid3D;
 pub type MTriangle = Triangle3D;
+pub type MTriMesh = TriMesh3D;
 pub type Ray = Ray3D;
+pub type Contact = Contact3D;
+pub type PointProjection = PointProjection3D;
+pub use parry3d::query::Unsupported;
+
+pub fn ray(origin: impl Into<Point3>, dir: impl Into<Vector3>) -> Ray {
+    Ray::new(origin.into(), dir.into())
+}
+
+// Equality within `epsilon`, per coordinate for points and vectors and in
+// radians for rotations.
+pub trait ApproxEq<Epsilon = Real> {
+    fn approx_eq(&self, other: &Self, epsilon: Epsilon) -> bool;
+}
+
+impl ApproxEq for Real {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        (self - other).abs() <= epsilon
+    }
+}
 
+impl ApproxEq for Vector3 {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        (self - other).amax() <= epsilon
+    }
+}
+
+impl ApproxEq for Point3 {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.coords.approx_eq(&other.coords, epsilon)
+    }
+}
+
+impl ApproxEq for Isometry {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.translation
+            .vector
+            .approx_eq(&other.translation.vector, epsilon)
+            && self.rotation.angle_to(&other.rotation) <= epsilon
+    }
+}
+
+impl ApproxEq for Ray {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.origin.approx_eq(&other.origin, epsilon) && self.dir.approx_eq(&other.dir, epsilon)
+    }
+}
+
+impl ApproxEq for Triangle {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0.a.approx_eq(&other.0.a, epsilon)
+            && self.0.b.approx_eq(&other.0.b, epsilon)
+            && self.0.c.approx_eq(&other.0.c, epsilon)
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq for Sphere {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0.radius.approx_eq(&other.0.radius, epsilon) && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq for Plane {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0.normal.approx_eq(&other.0.normal, epsilon) && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+// Convex polyhedrons are compared by their hull points, in any order.
+fn hull_approx_eq(p1: &[Point3], p2: &[Point3], epsilon: Real) -> bool {
+    p1.len() == p2.len()
+        && p1
+            .iter()
+            .all(|a| p2.iter().any(|b| a.approx_eq(b, epsilon)))
+}
+
+impl ApproxEq for Frustum {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        hull_approx_eq(self.0.points(), other.0.points(), epsilon)
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq for ConvexHull {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        hull_approx_eq(self.0.points(), other.0.points(), epsilon)
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq for TriMesh {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        let (v1, v2) = (self.0.vertices(), other.0.vertices());
+        v1.len() == v2.len()
+            && self.0.indices() == other.0.indices()
+            && v1.iter().zip(v2).all(|(a, b)| a.approx_eq(b, epsilon))
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq for Aabb {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.2.mins.approx_eq(&other.2.mins, epsilon)
+            && self.2.maxs.approx_eq(&other.2.maxs, epsilon)
+    }
+}
+
+impl ApproxEq for Obb {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0
+            .half_extents
+            .approx_eq(&other.0.half_extents, epsilon)
+            && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+impl ApproxEq for Custom {
+    fn approx_eq(&self, other: &Self, epsilon: Real) -> bool {
+        self.0 == other.0 && self.1.approx_eq(&other.1, epsilon)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Triangle(pub MTriangle, pub Isometry);
+#[derive(Debug, Clone, PartialEq)]
 pub struct Sphere(pub Ball, pub Isometry);
+// Infinite two sided plane through the isometry origin. Shapes touch it when they
+// cross it, the parry half space below it is only used by the explicit half space tests.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Plane(pub HalfSpace, pub Isometry);
+#[derive(Debug, Clone, PartialEq)]
 pub struct Frustum(pub ConvexPolyhedron, pub Isometry);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Front,
+    Back,
+    On,
+}
+
+impl Plane {
+    // None if `normal` is zero.
+    pub fn new(point: impl Into<Point3>, normal: impl Into<Vector3>) -> Option<Self> {
+        let point = point.into();
+        let normal = Unit::try_new(normal.into(), Real::EPSILON)?;
+        Some(Self(
+            HalfSpace::new(normal),
+            Isometry::translation(point.x, point.y, point.z),
+        ))
+    }
+
+    // Counter clockwise points face the front, None if they are collinear.
+    pub fn from_points(
+        a: impl Into<Point3>,
+        b: impl Into<Point3>,
+        c: impl Into<Point3>,
+    ) -> Option<Self> {
+        let (a, b, c) = (a.into(), b.into(), c.into());
+        Self::new(a, (b - a).cross(&(c - a)))
+    }
+
+    // Points with `normal·x + d = 0`, `normal` does not need to be normalized.
+    pub fn from_normal_and_d(normal: impl Into<Vector3>, d: Real) -> Option<Self> {
+        let normal = normal.into();
+        let len = normal.norm();
+        if len <= Real::EPSILON {
+            return None;
+        }
+
+        Self::new(Point3::from(normal * (-d / (len * len))), normal)
+    }
+
+    pub fn normal(&self) -> Unit<Vector3> {
+        self.1.rotation * self.0.normal
+    }
+
+    pub fn point(&self) -> Point3 {
+        Point3::from(self.1.translation.vector)
+    }
+
+    // `d` of `normal·x + d = 0`.
+    pub fn d(&self) -> Real {
+        -self.normal().dot(&self.point().coords)
+    }
+
+    // Positive in front of the plane.
+    pub fn signed_distance(&self, point: &Point3) -> Real {
+        self.normal().dot(&(point - self.point()))
+    }
+
+    pub fn project_point(&self, point: &Point3) -> Point3 {
+        point - self.normal().into_inner() * self.signed_distance(point)
+    }
+
+    pub fn side(&self, point: &Point3, epsilon: Real) -> Side {
+        let d = self.signed_distance(point);
+        if d > epsilon {
+            Side::Front
+        } else if d < -epsilon {
+            Side::Back
+        } else {
+            Side::On
+        }
+    }
+
+    // Hits the plane from either side.
+    pub fn cast_ray(&self, ray: &Ray, max_toi: Real) -> Option<Real> {
+        let d = self.signed_distance(&ray.origin);
+        if d == 0.0 {
+            return Some(0.0);
+        }
+
+        let speed = self.normal().dot(&ray.dir);
+        let toi = -d / speed;
+        (speed != 0.0 && toi >= 0.0 && toi <= max_toi).then_some(toi)
+    }
+
+    // The half spaces behind and in front of the plane.
+    pub fn half_spaces(&self) -> [HalfSpace; 2] {
+        [self.0, HalfSpace::new(-self.0.normal)]
+    }
+}
+
+impl Frustum {
+    // The frustum is the convex hull of `points`.
+    pub fn from_points<P: Into<Point3> + Copy>(points: &[P]) -> Option<Self> {
+        solid_convex_hull(points).map(|convex| Self(convex, Isometry::identity()))
+    }
+
+    pub fn from_corners<P: Into<Point3> + Copy>(near: [P; 4], far: [P; 4]) -> Option<Self> {
+        Self::from_points(&[
+            near[0], near[1], near[2], near[3], far[0], far[1], far[2], far[3],
+        ])
+    }
+
+    // Clip space depth in `[0, 1]`, as glam, wgpu and vulkan.
+    pub fn from_view_projection(view_proj: impl Into<Matrix4>) -> Option<Self> {
+        Self::from_clip_space(view_proj.into(), 0.0)
+    }
+
+    // Clip space depth in `[-1, 1]`, as opengl and nalgebra.
+    pub fn from_view_projection_gl(view_proj: impl Into<Matrix4>) -> Option<Self> {
+        Self::from_clip_space(view_proj.into(), -1.0)
+    }
+
+    fn from_clip_space(view_proj: Matrix4, near_z: Real) -> Option<Self> {
+        let inverse = view_proj.try_inverse()?;
+
+        let mut points = Vec::with_capacity(8);
+        for z in [near_z, 1.0] {
+            for (x, y) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                points.push(inverse.transform_point(&Point3::new(x, y, z)));
+            }
+        }
+        Self::from_points(&points)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexHull(pub ConvexPolyhedron, pub Isometry);
+
+impl ConvexHull {
+    // None when the points are repeated, flat or fewer than 4.
+    pub fn new<P: Into<Point3> + Copy>(points: &[P]) -> Option<Self> {
+        solid_convex_hull(points).map(|convex| Self(convex, Isometry::identity()))
+    }
+}
+
+// Hull enclosing a volume. Parry's `from_convex_hull` panics on repeated points and keeps flat hulls.
+pub(crate) fn solid_convex_hull<P: Into<Point3> + Copy>(points: &[P]) -> Option<ConvexPolyhedron> {
+    if points.len() < 4 {
+        return None;
+    }
+
+    let points: Vec<Point3> = points.iter().map(|p| (*p).into()).collect();
+    let (vertices, indices) = parry3d::transformation::try_convex_hull(&points).ok()?;
+    ConvexPolyhedron::from_convex_mesh(vertices, &indices)
+        .filter(|convex| convex.faces().len() >= 4)
+}
+
+// Triangle meshes are surfaces, shapes fully inside a closed mesh do not touch it.
+#[derive(Clone)]
+pub struct TriMesh(pub MTriMesh, pub Isometry);
+
+impl TriMesh {
+    pub fn new(vertices: Vec<Point3>, indices: Vec<[u32; 3]>) -> Option<Self> {
+        let len = vertices.len() as u32;
+        if indices.is_empty() || indices.iter().flatten().any(|i| *i >= len) {
+            return None;
+        }
+
+        Some(Self(MTriMesh::new(vertices, indices), Isometry::identity()))
+    }
+}
+
+impl std::fmt::Debug for TriMesh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TriMesh")
+            .field("vertices", &self.0.vertices().len())
+            .field("triangles", &self.0.indices().len())
+            .field("isometry", &self.1)
+            .finish()
+    }
+}
+
+impl PartialEq for TriMesh {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.vertices() == other.0.vertices()
+            && self.0.indices() == other.0.indices()
+            && self.1 == other.1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Aabb(pub Cuboid, pub Isometry, pub AABB);
 
+// The cuboid, its isometry and the parry aabb always describe the same box.
 impl Aabb {
-    pub fn new(mins: Point3, maxs: Point3) -> Self {
+    // Corners in any order, each axis is sorted.
+    pub fn new(mins: impl Into<Point3>, maxs: impl Into<Point3>) -> Self {
+        let (a, b) = (mins.into(), maxs.into());
+        let (mins, maxs) = (a.inf(&b), a.sup(&b));
         let r = (maxs - mins) * 0.5;
         let cuboid = Cuboid::new(r);
         let pos = Isometry::translation(mins.x + r.x, mins.y + r.y, mins.z + r.z);
@@ -35,17 +364,653 @@ impl Aabb {
 
         Self(cuboid, pos, aabb)
     }
+
+    // None if `points` is empty.
+    pub fn from_points<P: Into<Point3> + Copy>(points: &[P]) -> Option<Self> {
+        let first: Point3 = (*points.first()?).into();
+        let (mins, maxs) = points.iter().fold((first, first), |(mins, maxs), p| {
+            let p = (*p).into();
+            (mins.inf(&p), maxs.sup(&p))
+        });
+        Some(Self::new(mins, maxs))
+    }
+
+    pub fn mins(&self) -> Point3 {
+        self.2.mins
+    }
+
+    pub fn maxs(&self) -> Point3 {
+        self.2.maxs
+    }
+
+    pub fn center(&self) -> Point3 {
+        self.2.center()
+    }
+
+    // Full size along each axis.
+    pub fn extents(&self) -> Vector3 {
+        self.2.extents()
+    }
+
+    pub fn half_extents(&self) -> Vector3 {
+        self.2.half_extents()
+    }
+
+    pub fn volume(&self) -> Real {
+        let e = self.extents();
+        e.x * e.y * e.z
+    }
+
+    pub fn surface_area(&self) -> Real {
+        let e = self.extents();
+        2.0 * (e.x * e.y + e.y * e.z + e.z * e.x)
+    }
+
+    pub fn contains_aabb(&self, other: &Aabb) -> bool {
+        self.2.contains(&other.2)
+    }
+
+    // Smallest box holding both.
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        Self::new(
+            self.2.mins.inf(&other.2.mins),
+            self.2.maxs.sup(&other.2.maxs),
+        )
+    }
+
+    // None if the boxes do not touch.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        let (mins, maxs) = (
+            self.2.mins.sup(&other.2.mins),
+            self.2.maxs.inf(&other.2.maxs),
+        );
+        (mins <= maxs).then(|| Self::new(mins, maxs))
+    }
+
+    // Negative margins shrink the box, down to its center.
+    pub fn grow(&self, margin: Real) -> Aabb {
+        let margin = Vector3::repeat(margin).sup(&-self.half_extents());
+        Self::new(self.2.mins - margin, self.2.maxs + margin)
+    }
+
+    pub fn grow_to(&self, point: &Point3) -> Aabb {
+        Self::new(self.2.mins.inf(point), self.2.maxs.sup(point))
+    }
+
+    // Box enclosing this one once moved by `m`.
+    pub fn transform(&self, m: &Isometry) -> Aabb {
+        let aabb = self.2.transform_by(m);
+        Self::new(aabb.mins, aabb.maxs)
+    }
+
+    // Corner `i` is on the positive side of axis `k` when bit `k` of `i` is set.
+    pub fn corners(&self) -> [Point3; 8] {
+        let (mins, maxs) = (self.mins(), self.maxs());
+        std::array::from_fn(|i| {
+            let pick =
+                |bit: usize, min: Real, max: Real| if i & (1 << bit) == 0 { min } else { max };
+            Point3::new(
+                pick(0, mins.x, maxs.x),
+                pick(1, mins.y, maxs.y),
+                pick(2, mins.z, maxs.z),
+            )
+        })
+    }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Obb(pub Cuboid, pub Isometry);
 
 impl Obb {
-    pub fn new(pos: Point3, dir: Vector3, half_extents: Vector3) -> Self {
-        let cuboid = Cuboid::new(half_extents);
+    // `dir` is an axis-angle rotation vector, its length is the angle in radians.
+    pub fn new(
+        pos: impl Into<Point3>,
+        dir: impl Into<Vector3>,
+        half_extents: impl Into<Vector3>,
+    ) -> Self {
+        let (pos, dir) = (pos.into(), dir.into());
+        let cuboid = Cuboid::new(half_extents.into());
 
         Self(
             cuboid,
             Isometry::new(Vector3::new(pos.x, pos.y, pos.z), dir),
         )
     }
+
+    pub fn from_rotation(
+        center: impl Into<Point3>,
+        rotation: impl Into<Rotation>,
+        half_extents: impl Into<Vector3>,
+    ) -> Self {
+        let center = center.into();
+        Self(
+            Cuboid::new(half_extents.into()),
+            Isometry::from_parts(center.coords.into(), rotation.into()),
+        )
+    }
+
+    // The local x, y and z axes in world space. None unless they are orthonormal
+    // and right handed.
+    pub fn from_axes(
+        center: impl Into<Point3>,
+        axes: [Vector3; 3],
+        half_extents: impl Into<Vector3>,
+    ) -> Option<Self> {
+        const EPSILON: Real = 1.0e-4;
+        let [x, y, z] = axes;
+        let orthonormal = [x, y, z].iter().all(|a| (a.norm() - 1.0).abs() <= EPSILON)
+            && x.dot(&y).abs() <= EPSILON
+            && y.dot(&z).abs() <= EPSILON
+            && z.dot(&x).abs() <= EPSILON;
+        if !orthonormal || x.cross(&y).dot(&z) <= 0.0 {
+            return None;
+        }
+
+        let matrix = parry3d::na::Matrix3::from_columns(&axes);
+        let rotation = Rotation::from_matrix_eps(&matrix, EPSILON, 100, Rotation::identity());
+        Some(Self::from_rotation(center, rotation, half_extents))
+    }
+
+    // Rotations about x, then y, then z, in radians.
+    pub fn from_euler(
+        center: impl Into<Point3>,
+        roll: Real,
+        pitch: Real,
+        yaw: Real,
+        half_extents: impl Into<Vector3>,
+    ) -> Self {
+        let rotation = Rotation::from_euler_angles(roll, pitch, yaw);
+        Self::from_rotation(center, rotation, half_extents)
+    }
+
+    // The local z axis points along `dir` and the local y axis towards `up`.
+    // None if `dir` is zero or parallel to `up`.
+    pub fn look_at(
+        center: impl Into<Point3>,
+        dir: impl Into<Vector3>,
+        up: impl Into<Vector3>,
+        half_extents: impl Into<Vector3>,
+    ) -> Option<Self> {
+        let (dir, up) = (dir.into(), up.into());
+        if dir.cross(&up).norm_squared() <= Real::EPSILON {
+            return None;
+        }
+
+        let rotation = Rotation::face_towards(&dir, &up);
+        Some(Self::from_rotation(center, rotation, half_extents))
+    }
+
+    pub fn center(&self) -> Point3 {
+        Point3::from(self.1.translation.vector)
+    }
+
+    pub fn rotation(&self) -> Rotation {
+        self.1.rotation
+    }
+
+    // The local x, y and z axes in world space.
+    pub fn axes(&self) -> [Vector3; 3] {
+        [Vector3::x(), Vector3::y(), Vector3::z()].map(|a| self.1.rotation * a)
+    }
+
+    pub fn half_extents(&self) -> Vector3 {
+        self.0.half_extents
+    }
+
+    // Corner `i` is on the positive side of local axis `k` when bit `k` of `i` is set.
+    pub fn corners(&self) -> [Point3; 8] {
+        let h = self.0.half_extents;
+        std::array::from_fn(|i| {
+            let sign = |bit: usize| if i & (1 << bit) == 0 { -1.0 } else { 1.0 };
+            self.1 * Point3::new(sign(0) * h.x, sign(1) * h.y, sign(2) * h.z)
+        })
+    }
+}
+
+// User defined convex shape, queried through GJK and EPA on its support function.
+pub trait SupportShape: std::fmt::Debug + Send + Sync + 'static {
+    // Furthest local point in the direction `dir`, which is not normalized.
+    fn local_support_point(&self, dir: &Vector3) -> Point3;
+}
+
+// Shared between clones, which are the only shapes it compares equal to.
+#[derive(Debug, Clone)]
+pub struct CustomShape(pub Arc<dyn SupportShape>);
+
+impl CustomShape {
+    pub fn new(shape: impl SupportShape) -> Self {
+        Self(Arc::new(shape))
+    }
+}
+
+impl PartialEq for CustomShape {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl SupportMap for CustomShape {
+    fn local_support_point(&self, dir: &Vector3) -> Point3 {
+        self.0.local_support_point(dir)
+    }
+}
+
+impl PointQuery for CustomShape {
+    fn project_local_point(&self, pt: &Point3, solid: bool) -> PointProjection {
+        local_point_projection_on_support_map(self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+
+    fn project_local_point_and_get_feature(&self, pt: &Point3) -> (PointProjection, FeatureId) {
+        (self.project_local_point(pt, false), FeatureId::Unknown)
+    }
+}
+
+impl RayCast for CustomShape {
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        local_ray_intersection_with_support_map_with_params(
+            self,
+            &mut VoronoiSimplex::new(),
+            ray,
+            max_toi,
+            solid,
+        )
+    }
+}
+
+impl Shape for CustomShape {
+    fn compute_local_aabb(&self) -> AABB {
+        let x = self.0.local_support_point(&Vector3::x());
+        let y = self.0.local_support_point(&Vector3::y());
+        let z = self.0.local_support_point(&Vector3::z());
+        let nx = self.0.local_support_point(&-Vector3::x());
+        let ny = self.0.local_support_point(&-Vector3::y());
+        let nz = self.0.local_support_point(&-Vector3::z());
+        AABB::new(Point3::new(nx.x, ny.y, nz.z), Point3::new(x.x, y.y, z.z))
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        self.compute_local_aabb().bounding_sphere()
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    // Approximated by the bounding box.
+    fn mass_properties(&self, density: Real) -> MassProperties {
+        let aabb = self.compute_local_aabb();
+        let mut props = MassProperties::from_cuboid(density, aabb.half_extents());
+        props.local_com = aabb.center();
+        props
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> TypedShape<'_> {
+        TypedShape::Custom(0)
+    }
+
+    fn ccd_thickness(&self) -> Real {
+        self.compute_local_aabb().half_extents().min()
+    }
+
+    fn ccd_angular_thickness(&self) -> Real {
+        Real::frac_pi_4()
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap> {
+        Some(self as &dyn SupportMap)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Custom(pub CustomShape, pub Isometry);
+
+impl Custom {
+    pub fn new(shape: impl SupportShape, isometry: Isometry) -> Self {
+        Self(CustomShape::new(shape), isometry)
+    }
+}
+
+// Shared fixture, near plane at z = 0 and far plane at z = -5.
+#[cfg(test)]
+pub(crate) fn test_frustum_corners() -> ([Point3; 4], [Point3; 4]) {
+    (
+        [
+            Point3::new(2.0, 1.0, 0.0),
+            Point3::new(-2.0, 1.0, 0.0),
+            Point3::new(-2.0, -1.0, 0.0),
+            Point3::new(2.0, -1.0, 0.0),
+        ],
+        [
+            Point3::new(3.0, 2.0, -5.0),
+            Point3::new(-3.0, 2.0, -5.0),
+            Point3::new(-3.0, -2.0, -5.0),
+            Point3::new(3.0, -2.0, -5.0),
+        ],
+    )
+}
+
+#[cfg(test)]
+pub(crate) fn test_frustum() -> Frustum {
+    let (near, far) = test_frustum_corners();
+    Frustum::from_corners(near, far).unwrap()
+}
+
+#[cfg(test)]
+fn is_inside(frustum: &Frustum, p: Point3) -> bool {
+    frustum.0.contains_point(&frustum.1, &p)
+}
+
+#[test]
+fn test_frustum_from_view_projection() {
+    let proj = parry3d::na::Perspective3::new(1.0, 1.0, 1.0, 20.0).to_homogeneous();
+    let frustum = Frustum::from_view_projection_gl(proj).unwrap();
+
+    assert_eq!(is_inside(&frustum, Point3::new(0.0, 0.0, -5.0)), true);
+    assert_eq!(is_inside(&frustum, Point3::new(0.0, 0.0, 5.0)), false);
+    assert_eq!(is_inside(&frustum, Point3::new(0.0, 0.0, -0.5)), false);
+    assert_eq!(is_inside(&frustum, Point3::new(10.0, 0.0, -5.0)), false);
+
+    assert!(Frustum::from_view_projection(Matrix4::zeros()).is_none());
+}
+
+#[test]
+fn test_frustum_from_corners() {
+    let near = [
+        Point3::new(-1.0, -1.0, 0.0),
+        Point3::new(1.0, -1.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(-1.0, 1.0, 0.0),
+    ];
+    let far = near.map(|p| Point3::new(p.x * 2.0, p.y * 2.0, -5.0));
+    let frustum = Frustum::from_corners(near, far).unwrap();
+
+    assert_eq!(is_inside(&frustum, Point3::new(1.5, 0.0, -4.0)), true);
+    assert_eq!(is_inside(&frustum, Point3::new(1.5, 0.0, -1.0)), false);
+
+    // Degenerate corners give no frustum instead of panicking.
+    assert!(Frustum::from_corners(near, near).is_none());
+    assert!(Frustum::from_corners([Point3::origin(); 4], [Point3::origin(); 4]).is_none());
+}
+
+#[test]
+fn test_convex_hull_new() {
+    let cube = Aabb::new(Point3::origin(), Point3::new(1.0, 1.0, 1.0)).corners();
+    assert_eq!(ConvexHull::new(&cube).unwrap().0.points().len(), 8);
+
+    assert!(ConvexHull::new(&[Point3::origin(); 4]).is_none());
+    assert!(ConvexHull::new(&[Point3::new(1.0, 2.0, 3.0); 8]).is_none());
+    let quad = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.5, 0.5, 0.0),
+    ];
+    assert!(ConvexHull::new(&quad).is_none());
+    let line = [0.0, 1.0, 2.0, 3.0].map(|t| Point3::new(t, t, t));
+    assert!(ConvexHull::new(&line).is_none());
+    assert!(ConvexHull::new(&cube[..3]).is_none());
+}
+
+#[test]
+fn test_plane() {
+    let plane = Plane::new(Point3::new(0.0, 2.0, 0.0), Vector3::new(0.0, 3.0, 0.0)).unwrap();
+    assert_eq!(plane.normal().into_inner(), Vector3::y());
+    assert_eq!(plane.d(), -2.0);
+    assert_eq!(plane.signed_distance(&Point3::new(1.0, 5.0, 1.0)), 3.0);
+    assert_eq!(plane.signed_distance(&Point3::new(1.0, -1.0, 1.0)), -3.0);
+    assert_eq!(
+        plane.project_point(&Point3::new(1.0, 5.0, 1.0)),
+        Point3::new(1.0, 2.0, 1.0)
+    );
+    assert_eq!(plane.side(&Point3::new(0.0, 2.5, 0.0), 0.1), Side::Front);
+    assert_eq!(plane.side(&Point3::new(0.0, 1.5, 0.0), 0.1), Side::Back);
+    assert_eq!(plane.side(&Point3::new(9.0, 2.05, 0.0), 0.1), Side::On);
+
+    let points = Plane::from_points(
+        Point3::new(0.0, 2.0, 0.0),
+        Point3::new(0.0, 2.0, 1.0),
+        Point3::new(1.0, 2.0, 0.0),
+    )
+    .unwrap();
+    assert_eq!(points.approx_eq(&plane, 1.0e-6), true);
+
+    let d = Plane::from_normal_and_d(Vector3::new(0.0, 2.0, 0.0), -4.0).unwrap();
+    assert!(d.d().approx_eq(&-2.0, 1.0e-6));
+    assert!(d
+        .signed_distance(&Point3::new(0.0, 5.0, 0.0))
+        .approx_eq(&3.0, 1.0e-6));
+
+    assert!(Plane::new(Point3::origin(), Vector3::zeros()).is_none());
+    assert!(Plane::from_points(Point3::origin(), Point3::origin(), Point3::origin()).is_none());
+    assert!(Plane::from_normal_and_d(Vector3::zeros(), 1.0).is_none());
+
+    let down = Ray::new(Point3::new(0.0, 5.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+    let up = Ray::new(Point3::new(0.0, -1.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+    assert_eq!(plane.cast_ray(&down, Real::MAX), Some(3.0));
+    assert_eq!(plane.cast_ray(&up, Real::MAX), Some(3.0));
+    assert_eq!(plane.cast_ray(&up, 2.0), None);
+    assert_eq!(
+        plane.cast_ray(&Ray::new(Point3::origin(), Vector3::x()), Real::MAX),
+        None
+    );
+}
+
+#[test]
+fn test_aabb_algebra() {
+    let a = Aabb::new(Point3::new(2.0, 0.0, 1.0), Point3::new(0.0, 1.0, 0.0));
+    assert_eq!(a.mins(), Point3::new(0.0, 0.0, 0.0));
+    assert_eq!(a.maxs(), Point3::new(2.0, 1.0, 1.0));
+    assert_eq!(a.0.half_extents, Vector3::new(1.0, 0.5, 0.5));
+    assert_eq!(a.1.translation.vector, Vector3::new(1.0, 0.5, 0.5));
+    assert_eq!(a.center(), Point3::new(1.0, 0.5, 0.5));
+    assert_eq!(a.extents(), Vector3::new(2.0, 1.0, 1.0));
+    assert_eq!(a.volume(), 2.0);
+    assert_eq!(a.surface_area(), 10.0);
+    assert_eq!(a.corners()[0], a.mins());
+    assert_eq!(a.corners()[7], a.maxs());
+    assert_eq!(a.corners()[5], Point3::new(2.0, 0.0, 1.0));
+
+    let b = Aabb::new(Point3::new(1.0, 0.5, -1.0), Point3::new(3.0, 3.0, 0.5));
+    assert_eq!(
+        a.merge(&b),
+        Aabb::new(Point3::new(0.0, 0.0, -1.0), Point3::new(3.0, 3.0, 1.0))
+    );
+    assert_eq!(
+        a.intersection(&b),
+        Some(Aabb::new(
+            Point3::new(1.0, 0.5, 0.0),
+            Point3::new(2.0, 1.0, 0.5)
+        ))
+    );
+    let far = Aabb::new(Point3::new(5.0, 5.0, 5.0), Point3::new(6.0, 6.0, 6.0));
+    assert_eq!(a.intersection(&far), None);
+
+    assert_eq!(a.merge(&b).contains_aabb(&a), true);
+    assert_eq!(a.contains_aabb(&b), false);
+
+    assert_eq!(
+        a.grow(1.0),
+        Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(3.0, 2.0, 2.0))
+    );
+    assert_eq!(
+        a.grow(-0.75),
+        Aabb::new(Point3::new(0.75, 0.5, 0.5), Point3::new(1.25, 0.5, 0.5))
+    );
+    assert_eq!(
+        a.grow_to(&Point3::new(-1.0, 0.5, 4.0)),
+        Aabb::new(Point3::new(-1.0, 0.0, 0.0), Point3::new(2.0, 1.0, 4.0))
+    );
+
+    assert_eq!(
+        Aabb::from_points(&[
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(-1.0, 5.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0)
+        ]),
+        Some(Aabb::new(
+            Point3::new(-1.0, 0.0, 0.0),
+            Point3::new(1.0, 5.0, 3.0)
+        ))
+    );
+    assert_eq!(Aabb::from_points::<Point3>(&[]), None);
+
+    let quarter = std::f64::consts::FRAC_PI_2 as Real;
+    let moved = a.transform(&Isometry::new(
+        Vector3::new(10.0, 0.0, 0.0),
+        Vector3::new(0.0, 0.0, quarter),
+    ));
+    assert_eq!(
+        moved.approx_eq(
+            &Aabb::new(Point3::new(9.0, 0.0, 0.0), Point3::new(10.0, 2.0, 1.0)),
+            1.0e-5
+        ),
+        true
+    );
+    assert_eq!(moved.1.translation.vector, moved.center().coords);
+}
+
+#[test]
+fn test_obb_constructors() {
+    let half_extents = Vector3::new(1.0, 2.0, 3.0);
+    let quarter = std::f64::consts::FRAC_PI_2 as Real;
+
+    let axis_angle = Obb::new(
+        Point3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, 0.0, quarter),
+        half_extents,
+    );
+    let rotation = Obb::from_rotation(
+        Point3::new(1.0, 0.0, 0.0),
+        Rotation::from_axis_angle(&Vector3::z_axis(), quarter),
+        half_extents,
+    );
+    let euler = Obb::from_euler(Point3::new(1.0, 0.0, 0.0), 0.0, 0.0, quarter, half_extents);
+    let axes = Obb::from_axes(
+        Point3::new(1.0, 0.0, 0.0),
+        [Vector3::y(), -Vector3::x(), Vector3::z()],
+        half_extents,
+    )
+    .unwrap();
+    let look_at = Obb::look_at(
+        Point3::new(1.0, 0.0, 0.0),
+        Vector3::z(),
+        -Vector3::x(),
+        half_extents,
+    )
+    .unwrap();
+
+    for obb in [&rotation, &euler, &axes, &look_at] {
+        assert_eq!(obb.approx_eq(&axis_angle, 1.0e-3), true);
+    }
+
+    assert!(Obb::from_axes(Point3::origin(), [Vector3::x(); 3], half_extents).is_none());
+    assert!(Obb::from_axes(
+        Point3::origin(),
+        [Vector3::x(), Vector3::y(), -Vector3::z()],
+        half_extents
+    )
+    .is_none());
+    assert!(Obb::look_at(Point3::origin(), Vector3::y(), Vector3::y(), half_extents).is_none());
+}
+
+#[test]
+fn test_obb_accessors() {
+    let obb = Obb::from_euler(
+        Point3::new(1.0, 2.0, 3.0),
+        0.0,
+        0.0,
+        std::f64::consts::FRAC_PI_2 as Real,
+        Vector3::new(1.0, 2.0, 3.0),
+    );
+    assert_eq!(obb.center(), Point3::new(1.0, 2.0, 3.0));
+    assert_eq!(obb.half_extents(), Vector3::new(1.0, 2.0, 3.0));
+
+    let [x, y, z] = obb.axes();
+    assert_eq!(x.approx_eq(&Vector3::y(), 1.0e-6), true);
+    assert_eq!(y.approx_eq(&-Vector3::x(), 1.0e-6), true);
+    assert_eq!(z.approx_eq(&Vector3::z(), 1.0e-6), true);
+
+    let corners = obb.corners();
+    assert_eq!(
+        corners[0].approx_eq(&Point3::new(3.0, 1.0, 0.0), 1.0e-6),
+        true
+    );
+    assert_eq!(
+        corners[7].approx_eq(&Point3::new(-1.0, 3.0, 6.0), 1.0e-6),
+        true
+    );
+    for c in corners {
+        assert!(obb.0.contains_point(&obb.1, &c));
+        assert!((c - obb.center())
+            .norm()
+            .approx_eq(&Real::sqrt(14.0), 1.0e-5));
+    }
+}
+
+#[cfg(all(feature = "glam", not(feature = "f64")))]
+#[test]
+fn test_glam_constructors() {
+    use glam::{Mat4, Vec3};
+
+    let aabb = Aabb::new(Vec3::ZERO, Vec3::ONE);
+    assert_eq!(aabb.2.maxs, Point3::new(1.0, 1.0, 1.0));
+
+    let obb = Obb::new(Vec3::new(1.0, 2.0, 3.0), Vec3::ZERO, Vec3::splat(0.5));
+    assert_eq!(obb.1.translation.vector, Vector3::new(1.0, 2.0, 3.0));
+
+    let r = ray(Vec3::ZERO, Vec3::Z);
+    assert_eq!(r.dir, Vector3::z());
+
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+    let proj = Mat4::perspective_rh(1.0, 1.0, 1.0, 20.0);
+    let frustum = Frustum::from_view_projection(proj * view).unwrap();
+    assert_eq!(is_inside(&frustum, Point3::origin()), true);
+    assert_eq!(is_inside(&frustum, Point3::new(0.0, 0.0, 9.5)), false);
+    assert_eq!(is_inside(&frustum, Point3::new(0.0, 0.0, 11.0)), false);
+}
+
+#[cfg(feature = "mint")]
+#[test]
+fn test_mint_constructors() {
+    let aabb = Aabb::new(
+        mint::Point3 {
+            x: -1.0,
+            y: -1.0,
+            z: -1.0,
+        },
+        mint::Point3 {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0,
+        },
+    );
+    assert_eq!(aabb.2.mins, Point3::new(-1.0, -1.0, -1.0));
+
+    let r = ray(
+        mint::Point3 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        },
+        mint::Vector3 {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        },
+    );
+    assert_eq!(r.dir, Vector3::x());
 }
@@ -0,0 +1,493 @@
+#[cfg(not(feature = "f64"))]
+use wide::f32x8;
+#[cfg(feature = "f64")]
+use wide::f64x4;
+use wide::CmpGt;
+
+use crate::shape::{Aabb, Frustum, Obb, Point3, Real, Sphere, Triangle, Vector3};
+
+#[allow(unused_imports)]
+use crate::shape::{Ball, ConvexPolyhedron, Isometry, MTriangle};
+
+#[cfg(not(feature = "f64"))]
+type Lanes = f32x8;
+#[cfg(not(feature = "f64"))]
+const LANES: usize = 8;
+
+#[cfg(feature = "f64")]
+type Lanes = f64x4;
+#[cfg(feature = "f64")]
+const LANES: usize = 4;
+
+// Planes are stored as `n·x + d`, with the frustum interior on the negative side.
+pub struct FrustumPlanes {
+    pub normals: Vec<Vector3>,
+    pub ds: Vec<Real>,
+}
+
+impl FrustumPlanes {
+    pub fn new(frustum: &Frustum) -> Self {
+        let convex = &frustum.0;
+        let points = convex.points();
+        let center = points
+            .iter()
+            .fold(Vector3::zeros(), |acc, p| acc + p.coords)
+            / points.len() as Real;
+        let center = frustum.1 * Point3::from(center);
+
+        let mut normals = Vec::with_capacity(convex.faces().len());
+        let mut ds = Vec::with_capacity(convex.faces().len());
+        for face in convex.faces() {
+            let vertex = convex.vertices_adj_to_face()[face.first_vertex_or_edge as usize];
+            let point = frustum.1 * points[vertex as usize];
+            let mut normal = frustum.1 * face.normal.into_inner();
+            let mut d = -normal.dot(&point.coords);
+
+            // Face winding is not guaranteed, orient every plane away from the center.
+            if normal.dot(&center.coords) + d > 0.0 {
+                normal = -normal;
+                d = -d;
+            }
+
+            normals.push(normal);
+            ds.push(d);
+        }
+
+        Self { normals, ds }
+    }
+
+    pub fn len(&self) -> usize {
+        self.normals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.normals.is_empty()
+    }
+
+    // Plane based tests against single shapes, faster than the exact tests of
+    // the crate root. Shapes are only rejected when fully outside one plane, so
+    // shapes near the edges of the frustum may be kept.
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        let (center, axes, half) = (obb.center(), obb.axes(), obb.half_extents());
+        self.normals.iter().zip(self.ds.iter()).all(|(n, d)| {
+            let radius = (0..3)
+                .map(|i| half[i] * n.dot(&axes[i]).abs())
+                .sum::<Real>();
+            n.dot(&center.coords) + d <= radius
+        })
+    }
+
+    pub fn intersects_triangle(&self, tri: &Triangle) -> bool {
+        !self.outside(&tri.0.vertices().map(|p| tri.1 * p))
+    }
+
+    // Only the planes of `self` are tested, swap the frustums for a tighter test.
+    pub fn intersects_frustum(&self, frustum: &Frustum) -> bool {
+        let points: Vec<Point3> = frustum.0.points().iter().map(|p| frustum.1 * p).collect();
+        !self.outside(&points)
+    }
+
+    fn outside(&self, points: &[Point3]) -> bool {
+        self.normals
+            .iter()
+            .zip(self.ds.iter())
+            .any(|(n, d)| points.iter().all(|p| n.dot(&p.coords) + d > 0.0))
+    }
+}
+
+pub struct Visibility {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Visibility {
+    pub fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn reset(&mut self, len: usize) {
+        self.words.clear();
+        self.words.resize(len.div_ceil(64), 0);
+        self.len = len;
+    }
+
+    fn set_lanes(&mut self, start: usize, mask: u32) {
+        let (word, bit) = (start / 64, start % 64);
+        self.words[word] |= (mask as u64) << bit;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(
+            index < self.len,
+            "visibility index out of range!!! index: {}, len: {}",
+            index,
+            self.len
+        );
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn count_visible(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn iter_visible(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(move |i| self.get(*i))
+    }
+
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn load(values: &[Real], start: usize) -> Lanes {
+    let mut lanes = [0.0; LANES];
+    let end = (start + LANES).min(values.len());
+    lanes[..end - start].copy_from_slice(&values[start..end]);
+    Lanes::from(lanes)
+}
+
+fn lane_mask(len: usize, start: usize) -> u32 {
+    let count = (len - start).min(LANES);
+    (1u32 << count) - 1
+}
+
+#[derive(Default)]
+pub struct AabbSoa {
+    pub center_x: Vec<Real>,
+    pub center_y: Vec<Real>,
+    pub center_z: Vec<Real>,
+    pub half_x: Vec<Real>,
+    pub half_y: Vec<Real>,
+    pub half_z: Vec<Real>,
+}
+
+impl AabbSoa {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            center_x: Vec::with_capacity(capacity),
+            center_y: Vec::with_capacity(capacity),
+            center_z: Vec::with_capacity(capacity),
+            half_x: Vec::with_capacity(capacity),
+            half_y: Vec::with_capacity(capacity),
+            half_z: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, aabb: &Aabb) {
+        self.push_min_max(&aabb.2.mins, &aabb.2.maxs);
+    }
+
+    pub fn push_min_max(&mut self, mins: &Point3, maxs: &Point3) {
+        let center = parry3d::na::center(mins, maxs);
+        let half = (maxs - mins) * 0.5;
+        self.center_x.push(center.x);
+        self.center_y.push(center.y);
+        self.center_z.push(center.z);
+        self.half_x.push(half.x.abs());
+        self.half_y.push(half.y.abs());
+        self.half_z.push(half.z.abs());
+    }
+
+    pub fn len(&self) -> usize {
+        self.center_x.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.center_x.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.center_x.clear();
+        self.center_y.clear();
+        self.center_z.clear();
+        self.half_x.clear();
+        self.half_y.clear();
+        self.half_z.clear();
+    }
+
+    pub fn cull(&self, planes: &FrustumPlanes) -> Visibility {
+        let mut out = Visibility::new();
+        self.cull_into(planes, &mut out);
+        out
+    }
+
+    pub fn cull_into(&self, planes: &FrustumPlanes, out: &mut Visibility) {
+        let len = self.len();
+        out.reset(len);
+
+        for start in (0..len).step_by(LANES) {
+            let (cx, cy, cz) = (
+                load(&self.center_x, start),
+                load(&self.center_y, start),
+                load(&self.center_z, start),
+            );
+            let (hx, hy, hz) = (
+                load(&self.half_x, start),
+                load(&self.half_y, start),
+                load(&self.half_z, start),
+            );
+
+            let mut outside = Lanes::splat(0.0);
+            for (n, d) in planes.normals.iter().zip(planes.ds.iter()) {
+                let dist = cx * n.x + cy * n.y + cz * n.z + *d;
+                let radius = hx * n.x.abs() + hy * n.y.abs() + hz * n.z.abs();
+                outside |= dist.cmp_gt(radius);
+            }
+
+            let visible = !(outside.move_mask() as u32) & lane_mask(len, start);
+            out.set_lanes(start, visible);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SphereSoa {
+    pub center_x: Vec<Real>,
+    pub center_y: Vec<Real>,
+    pub center_z: Vec<Real>,
+    pub radius: Vec<Real>,
+}
+
+impl SphereSoa {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            center_x: Vec::with_capacity(capacity),
+            center_y: Vec::with_capacity(capacity),
+            center_z: Vec::with_capacity(capacity),
+            radius: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, sphere: &Sphere) {
+        let center = sphere.1.translation.vector;
+        self.push_center_radius(&Point3::from(center), sphere.0.radius);
+    }
+
+    pub fn push_center_radius(&mut self, center: &Point3, radius: Real) {
+        self.center_x.push(center.x);
+        self.center_y.push(center.y);
+        self.center_z.push(center.z);
+        self.radius.push(radius);
+    }
+
+    pub fn len(&self) -> usize {
+        self.center_x.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.center_x.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.center_x.clear();
+        self.center_y.clear();
+        self.center_z.clear();
+        self.radius.clear();
+    }
+
+    pub fn cull(&self, planes: &FrustumPlanes) -> Visibility {
+        let mut out = Visibility::new();
+        self.cull_into(planes, &mut out);
+        out
+    }
+
+    pub fn cull_into(&self, planes: &FrustumPlanes, out: &mut Visibility) {
+        let len = self.len();
+        out.reset(len);
+
+        for start in (0..len).step_by(LANES) {
+            let (cx, cy, cz) = (
+                load(&self.center_x, start),
+                load(&self.center_y, start),
+                load(&self.center_z, start),
+            );
+            let radius = load(&self.radius, start);
+
+            let mut outside = Lanes::splat(0.0);
+            for (n, d) in planes.normals.iter().zip(planes.ds.iter()) {
+                let dist = cx * n.x + cy * n.y + cz * n.z + *d;
+                outside |= dist.cmp_gt(radius);
+            }
+
+            let visible = !(outside.move_mask() as u32) & lane_mask(len, start);
+            out.set_lanes(start, visible);
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::shape::test_frustum;
+
+#[test]
+fn test_frustum_planes() {
+    let planes = FrustumPlanes::new(&test_frustum());
+    assert!(planes.len() >= 6);
+
+    let inside = Point3::new(0.0, 0.0, -1.0);
+    for (n, d) in planes.normals.iter().zip(planes.ds.iter()) {
+        assert!(n.dot(&inside.coords) + d <= 0.0);
+    }
+}
+
+#[test]
+fn test_aabb_soa_cull() {
+    let frustum = test_frustum();
+    let planes = FrustumPlanes::new(&frustum);
+
+    let mut soa = AabbSoa::new();
+    for i in 0..20 {
+        let z = -(i as Real);
+        soa.push(&Aabb::new(
+            Point3::new(-0.5, -0.5, z - 0.5),
+            Point3::new(0.5, 0.5, z + 0.5),
+        ));
+    }
+    soa.push(&Aabb::new(
+        Point3::new(10.0, 0.0, -3.0),
+        Point3::new(11.0, 1.0, -2.0),
+    ));
+
+    let visible = soa.cull(&planes);
+    assert_eq!(visible.len(), 21);
+    for i in 0..20 {
+        assert_eq!(visible.get(i), i <= 5, "aabb {}", i);
+    }
+    assert_eq!(visible.get(20), false);
+    assert_eq!(visible.count_visible(), 6);
+    assert_eq!(
+        visible.iter_visible().collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4, 5]
+    );
+
+    for i in 0..soa.len() {
+        let aabb = Aabb::new(
+            Point3::new(
+                soa.center_x[i] - soa.half_x[i],
+                soa.center_y[i] - soa.half_y[i],
+                soa.center_z[i] - soa.half_z[i],
+            ),
+            Point3::new(
+                soa.center_x[i] + soa.half_x[i],
+                soa.center_y[i] + soa.half_y[i],
+                soa.center_z[i] + soa.half_z[i],
+            ),
+        );
+        assert_eq!(
+            visible.get(i),
+            crate::compute_aabb_and_frustum(&aabb, &frustum)
+        );
+    }
+}
+
+#[test]
+fn test_sphere_soa_cull() {
+    let frustum = test_frustum();
+    let planes = FrustumPlanes::new(&frustum);
+
+    let mut soa = SphereSoa::with_capacity(3);
+    soa.push(&Sphere(Ball::new(1.0), Isometry::identity()));
+    soa.push(&Sphere(
+        Ball::new(1.0),
+        Isometry::translation(0.0, 0.0, 2.0),
+    ));
+    soa.push(&Sphere(
+        Ball::new(1.0),
+        Isometry::translation(0.0, 0.0, -3.0),
+    ));
+
+    let visible = soa.cull(&planes);
+    assert_eq!(visible.get(0), true);
+    assert_eq!(visible.get(1), false);
+    assert_eq!(visible.get(2), true);
+
+    soa.clear();
+    assert_eq!(soa.cull(&planes).is_empty(), true);
+}
+
+#[test]
+fn test_frustum_planes_shapes() {
+    let frustum = test_frustum();
+    let planes = FrustumPlanes::new(&frustum);
+
+    for i in 0..16 {
+        let angle = i as Real * 0.4;
+        let center = Point3::new(angle.cos() * 3.0, angle.sin() * 2.0, -(i as Real) * 0.5);
+
+        let obb = Obb::new(
+            center,
+            Vector3::new(angle, 0.5, 0.0),
+            Vector3::new(0.5, 1.0, 0.25),
+        );
+        // Approximations never reject what the exact test keeps.
+        if crate::compute_obb_and_frustum(&obb, &frustum) {
+            assert_eq!(planes.intersects_obb(&obb), true, "obb {}", i);
+        }
+
+        let tri = Triangle(
+            MTriangle::new(
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.5, 0.0),
+                Point3::new(0.0, 1.0, 1.0),
+            ),
+            Isometry::new(center.coords, Vector3::new(0.0, angle, 0.0)),
+        );
+        if crate::compute_triangle_and_frustum(&tri, &frustum) {
+            assert_eq!(planes.intersects_triangle(&tri), true, "triangle {}", i);
+        }
+    }
+
+    let obb = Obb::new(
+        Point3::new(0.0, 0.0, -2.0),
+        Vector3::new(0.0, 0.5, 0.0),
+        Vector3::new(1.0, 1.0, 1.0),
+    );
+    assert_eq!(planes.intersects_obb(&obb), true);
+    let obb = Obb::new(
+        Point3::new(0.0, 0.0, 2.0),
+        Vector3::new(0.0, 0.5, 0.0),
+        Vector3::new(1.0, 1.0, 1.0),
+    );
+    assert_eq!(planes.intersects_obb(&obb), false);
+
+    let tri = Triangle(
+        MTriangle::new(
+            Point3::new(-10.0, 0.0, -2.0),
+            Point3::new(10.0, 0.0, -2.0),
+            Point3::new(0.0, 0.0, 10.0),
+        ),
+        Isometry::identity(),
+    );
+    assert_eq!(planes.intersects_triangle(&tri), true);
+    let tri = Triangle(tri.0, Isometry::translation(0.0, 10.0, 0.0));
+    assert_eq!(planes.intersects_triangle(&tri), false);
+
+    let other = Frustum(frustum.0.clone(), Isometry::translation(0.0, 0.0, -4.0));
+    assert_eq!(planes.intersects_frustum(&other), true);
+    let other = Frustum(frustum.0.clone(), Isometry::translation(0.0, 0.0, -5.5));
+    assert_eq!(planes.intersects_frustum(&other), false);
+}
@@ -0,0 +1,345 @@
+use wasm_bindgen::prelude::*;
+
+use crate::batch;
+use crate::shape::{
+    Aabb, Ball, ConvexHull, Frustum, Isometry, MTriangle, Obb, Plane, Point3, Ray, Real, Sphere,
+    TriMesh, Triangle, Vector3,
+};
+use crate::Geomery;
+
+#[wasm_bindgen]
+pub struct Shape {
+    geomery: Geomery,
+}
+
+#[wasm_bindgen]
+impl Shape {
+    pub fn point(x: Real, y: Real, z: Real) -> Shape {
+        Shape {
+            geomery: Geomery::Point(Point3::new(x, y, z)),
+        }
+    }
+
+    pub fn ray(ox: Real, oy: Real, oz: Real, dx: Real, dy: Real, dz: Real) -> Shape {
+        Shape {
+            geomery: Geomery::Ray(Ray::new(Point3::new(ox, oy, oz), Vector3::new(dx, dy, dz))),
+        }
+    }
+
+    pub fn plane(nx: Real, ny: Real, nz: Real, px: Real, py: Real, pz: Real) -> Option<Shape> {
+        let plane = Plane::new(Point3::new(px, py, pz), Vector3::new(nx, ny, nz))?;
+        Some(Shape {
+            geomery: Geomery::Plane(plane),
+        })
+    }
+
+    pub fn sphere(x: Real, y: Real, z: Real, radius: Real) -> Shape {
+        Shape {
+            geomery: Geomery::Sphere(Sphere(Ball::new(radius), Isometry::translation(x, y, z))),
+        }
+    }
+
+    pub fn ball(radius: Real) -> Shape {
+        Shape {
+            geomery: Geomery::Ball(Ball::new(radius)),
+        }
+    }
+
+    pub fn aabb(
+        min_x: Real,
+        min_y: Real,
+        min_z: Real,
+        max_x: Real,
+        max_y: Real,
+        max_z: Real,
+    ) -> Shape {
+        Shape {
+            geomery: Geomery::Aabb(Aabb::new(
+                Point3::new(min_x, min_y, min_z),
+                Point3::new(max_x, max_y, max_z),
+            )),
+        }
+    }
+
+    // `rx, ry, rz` is an axis-angle rotation, as in `Obb::new`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn obb(
+        x: Real,
+        y: Real,
+        z: Real,
+        rx: Real,
+        ry: Real,
+        rz: Real,
+        hx: Real,
+        hy: Real,
+        hz: Real,
+    ) -> Shape {
+        Shape {
+            geomery: Geomery::Obb(Obb::new(
+                Point3::new(x, y, z),
+                Vector3::new(rx, ry, rz),
+                Vector3::new(hx, hy, hz),
+            )),
+        }
+    }
+
+    // `points` holds the three vertices as `[ax, ay, az, bx, by, bz, cx, cy, cz]`.
+    pub fn triangle(points: &[Real]) -> Option<Shape> {
+        if points.len() != 9 {
+            return None;
+        }
+
+        let p = to_points(points);
+        Some(Shape {
+            geomery: Geomery::Triangle(Triangle(
+                MTriangle::new(p[0], p[1], p[2]),
+                Isometry::identity(),
+            )),
+        })
+    }
+
+    // `points` is a flat `xyz` list, the frustum is their convex hull. Throws when the
+    // points are repeated or flat.
+    pub fn frustum(points: &[Real]) -> Result<Shape, JsError> {
+        frustum_shape(points).ok_or_else(|| JsError::new("invalid frustum points"))
+    }
+
+    // `vertices` is a flat `xyz` list and `indices` holds three entries per triangle.
+    pub fn trimesh(vertices: &[Real], indices: &[u32]) -> Option<Shape> {
+        if !vertices.len().is_multiple_of(3) || !indices.len().is_multiple_of(3) {
+            return None;
+        }
+
+        let indices = indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect();
+        Some(Shape {
+            geomery: Geomery::TriMesh(TriMesh::new(to_points(vertices), indices)?),
+        })
+    }
+
+    pub fn convex_hull(points: &[Real]) -> Option<Shape> {
+        if !points.len().is_multiple_of(3) {
+            return None;
+        }
+
+        Some(Shape {
+            geomery: Geomery::ConvexHull(ConvexHull::new(&to_points(points))?),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        self.geomery.kind().to_string()
+    }
+}
+
+fn to_points(values: &[Real]) -> Vec<Point3> {
+    values
+        .chunks_exact(3)
+        .map(|p| Point3::new(p[0], p[1], p[2]))
+        .collect()
+}
+
+#[wasm_bindgen]
+pub struct ContactResult {
+    point1: Point3,
+    point2: Point3,
+    normal1: Vector3,
+    normal2: Vector3,
+    dist: Real,
+}
+
+#[wasm_bindgen]
+impl ContactResult {
+    #[wasm_bindgen(getter)]
+    pub fn point1(&self) -> Vec<Real> {
+        self.point1.coords.as_slice().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn point2(&self) -> Vec<Real> {
+        self.point2.coords.as_slice().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn normal1(&self) -> Vec<Real> {
+        self.normal1.as_slice().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn normal2(&self) -> Vec<Real> {
+        self.normal2.as_slice().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn dist(&self) -> Real {
+        self.dist
+    }
+}
+
+// Unsupported pairs return `undefined` instead of throwing.
+#[wasm_bindgen(js_name = testCollision)]
+pub fn test_collision(a: &Shape, b: &Shape) -> Option<bool> {
+    crate::try_test_collision(&a.geomery, &b.geomery)
+}
+
+#[wasm_bindgen]
+pub fn raycast(ray: &Shape, target: &Shape, max_toi: Real) -> Option<Real> {
+    match &ray.geomery {
+        Geomery::Ray(r) => crate::raycast(r, &target.geomery, max_toi),
+        _ => None,
+    }
+}
+
+#[wasm_bindgen]
+pub fn distance(a: &Shape, b: &Shape) -> Option<Real> {
+    crate::distance(&a.geomery, &b.geomery).ok()
+}
+
+#[wasm_bindgen]
+pub fn contact(a: &Shape, b: &Shape, prediction: Real) -> Option<ContactResult> {
+    let c = crate::contact(&a.geomery, &b.geomery, prediction).ok()??;
+    Some(ContactResult {
+        point1: c.point1,
+        point2: c.point2,
+        normal1: c.normal1.into_inner(),
+        normal2: c.normal2.into_inner(),
+        dist: c.dist,
+    })
+}
+
+// `spheres` is a flat `[x, y, z, radius]` list, one visibility byte per sphere.
+#[wasm_bindgen(js_name = cullSpheres)]
+pub fn cull_spheres(frustum: &Shape, spheres: &[Real]) -> Option<Vec<u8>> {
+    if !spheres.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let geomeries: Vec<Geomery> = spheres
+        .chunks_exact(4)
+        .map(|s| {
+            Geomery::Sphere(Sphere(
+                Ball::new(s[3]),
+                Isometry::translation(s[0], s[1], s[2]),
+            ))
+        })
+        .collect();
+    cull(frustum, &geomeries)
+}
+
+// `aabbs` is a flat `[min_x, min_y, min_z, max_x, max_y, max_z]` list.
+#[wasm_bindgen(js_name = cullAabbs)]
+pub fn cull_aabbs(frustum: &Shape, aabbs: &[Real]) -> Option<Vec<u8>> {
+    if !aabbs.len().is_multiple_of(6) {
+        return None;
+    }
+
+    let geomeries: Vec<Geomery> = aabbs
+        .chunks_exact(6)
+        .map(|a| {
+            Geomery::Aabb(Aabb::new(
+                Point3::new(a[0], a[1], a[2]),
+                Point3::new(a[3], a[4], a[5]),
+            ))
+        })
+        .collect();
+    cull(frustum, &geomeries)
+}
+
+fn frustum_shape(points: &[Real]) -> Option<Shape> {
+    if !points.len().is_multiple_of(3) {
+        return None;
+    }
+
+    Some(Shape {
+        geomery: Geomery::Frustum(Frustum::from_points(&to_points(points))?),
+    })
+}
+
+fn cull(frustum: &Shape, geomeries: &[Geomery]) -> Option<Vec<u8>> {
+    let frustum = match &frustum.geomery {
+        Geomery::Frustum(f) => f,
+        _ => return None,
+    };
+
+    let mut out = vec![false; geomeries.len()];
+    batch::cull(frustum, geomeries, &mut out);
+    Some(out.into_iter().map(u8::from).collect())
+}
+
+#[cfg(test)]
+fn test_frustum() -> Shape {
+    Shape {
+        geomery: Geomery::Frustum(crate::shape::test_frustum()),
+    }
+}
+
+#[test]
+fn test_wasm_test_collision() {
+    let sphere = Shape::sphere(0.0, 0.0, 0.0, 1.0);
+
+    assert_eq!(
+        test_collision(&Shape::point(0.5, 0.0, 0.0), &sphere),
+        Some(true)
+    );
+    assert_eq!(
+        test_collision(&sphere, &Shape::aabb(2.0, 2.0, 2.0, 3.0, 3.0, 3.0)),
+        Some(false)
+    );
+    assert_eq!(test_collision(&sphere, &Shape::point(0.5, 0.0, 0.0)), None);
+}
+
+#[test]
+fn test_wasm_queries() {
+    let sphere = Shape::sphere(3.0, 0.0, 0.0, 1.0);
+    let ray = Shape::ray(0.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+    assert_eq!(raycast(&ray, &sphere, Real::MAX), Some(2.0));
+    assert_eq!(raycast(&sphere, &sphere, Real::MAX), None);
+
+    let other = Shape::sphere(0.0, 0.0, 0.0, 1.0);
+    assert_eq!(distance(&sphere, &other), Some(1.0));
+    assert_eq!(distance(&ray, &other), None);
+
+    let c = contact(&other, &Shape::sphere(1.5, 0.0, 0.0, 1.0), 0.0).unwrap();
+    assert_eq!(c.dist(), -0.5);
+    assert_eq!(c.normal1(), vec![1.0, 0.0, 0.0]);
+    assert!(contact(&other, &sphere, 0.0).is_none());
+}
+
+#[test]
+fn test_wasm_constructors() {
+    assert!(Shape::plane(0.0, 0.0, 0.0, 0.0, 0.0, 0.0).is_none());
+    assert!(Shape::triangle(&[0.0; 6]).is_none());
+    // Errors are js values, only the checks behind them run natively.
+    assert!(frustum_shape(&[0.0; 6]).is_none());
+    assert!(frustum_shape(&[1.0; 12]).is_none());
+    #[rustfmt::skip]
+    assert!(frustum_shape(&[
+        0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+    ]).is_none());
+    let (near, far) = crate::shape::test_frustum_corners();
+    let points: Vec<Real> = near
+        .iter()
+        .chain(&far)
+        .flat_map(|p| [p.x, p.y, p.z])
+        .collect();
+    assert_eq!(Shape::frustum(&points).unwrap().kind(), "Frustum");
+}
+
+#[test]
+fn test_wasm_cull() {
+    let frustum = test_frustum();
+
+    let spheres = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 1.0];
+    assert_eq!(cull_spheres(&frustum, &spheres), Some(vec![1, 0]));
+
+    let aabbs = [
+        0.0, 0.0, -3.0, 1.0, 1.0, -2.0, 10.0, 0.0, -3.0, 11.0, 1.0, -2.0,
+    ];
+    assert_eq!(cull_aabbs(&frustum, &aabbs), Some(vec![1, 0]));
+
+    assert_eq!(cull_aabbs(&frustum, &aabbs[..5]), None);
+    assert_eq!(cull_spheres(&Shape::ball(1.0), &spheres), None);
+}
@@ -0,0 +1,45 @@
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use pi_collision::shape::Real;
+use pi_collision::wasm::{cull_spheres, distance, raycast, test_collision, Shape};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_collision_in_wasm() {
+    let sphere = Shape::sphere(0.0, 0.0, 0.0, 1.0);
+
+    assert_eq!(
+        test_collision(&Shape::point(0.5, 0.0, 0.0), &sphere),
+        Some(true)
+    );
+    assert_eq!(
+        test_collision(&sphere, &Shape::aabb(2.0, 2.0, 2.0, 3.0, 3.0, 3.0)),
+        Some(false)
+    );
+    assert_eq!(test_collision(&sphere, &Shape::point(0.5, 0.0, 0.0)), None);
+}
+
+#[wasm_bindgen_test]
+fn test_queries_in_wasm() {
+    let sphere = Shape::sphere(3.0, 0.0, 0.0, 1.0);
+    let ray = Shape::ray(0.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+    assert_eq!(raycast(&ray, &sphere, Real::MAX), Some(2.0));
+    assert_eq!(
+        distance(&sphere, &Shape::sphere(0.0, 0.0, 0.0, 1.0)),
+        Some(1.0)
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_cull_in_wasm() {
+    #[rustfmt::skip]
+    let points = [
+        2.0, 1.0, 0.0, -2.0, 1.0, 0.0, -2.0, -1.0, 0.0, 2.0, -1.0, 0.0,
+        3.0, 2.0, -5.0, -3.0, 2.0, -5.0, -3.0, -2.0, -5.0, 3.0, -2.0, -5.0,
+    ];
+    let frustum = Shape::frustum(&points).unwrap();
+    assert!(Shape::frustum(&[1.0; 12]).is_err());
+
+    let spheres = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 1.0];
+    assert_eq!(cull_spheres(&frustum, &spheres), Some(vec![1, 0]));
+}